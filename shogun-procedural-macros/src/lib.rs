@@ -12,11 +12,13 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
     let mut lower_name_string = "name".to_string();
     let mut create_name_string = "create".to_string();
+    let mut sg_type_string = "SG_TYPE_SG".to_string();
 
     for c in name.to_string().chars() {
         if c.is_uppercase() {
             lower_name_string.push('_');
             create_name_string.push('_');
+            sg_type_string.push('_');
             lower_name_string.push_str(&c.to_lowercase().to_string());
             create_name_string.push_str(&c.to_lowercase().to_string());
         }
@@ -24,59 +26,114 @@ pub fn derive(input: TokenStream) -> TokenStream {
             lower_name_string.push(c);
             create_name_string.push(c);
         }
+        sg_type_string.push_str(&c.to_uppercase().to_string());
     }
 
     let lower_name_ident = Ident::new(&lower_name_string, name.span());
     let create_name_ident = Ident::new(&create_name_string, name.span());
+    let sg_type_ident = Ident::new(&sg_type_string, name.span());
 
     let tokens = quote! {
         impl #name {
-            pub fn new(#lower_name_ident: &'static str) -> Result<Self, String> {
+            pub fn new(#lower_name_ident: &str) -> Result<Self, ShogunError> {
                 #name::create(#lower_name_ident)
             }
+
+            /// Builder that creates the object and puts its parameters at once
+            pub fn builder<'a>(#lower_name_ident: &str) -> SGObjectBuilder<'a, #name> {
+                SGObjectBuilder::new(#lower_name_ident)
+            }
+
+            /// Names of the classes in Shogun's class registry. The registry
+            /// does not know the base class of a class, so classes of other
+            /// base types are listed as well and new rejects them with
+            /// TypeMismatch.
+            pub fn available_classes() -> Vec<String> {
+                let c_list = unsafe { shogun_sys::available_classes() };
+                details::string_list_to_vec(c_list)
+            }
         }
 
         impl SGObject for #name {
             type DerivedObject = #name;
-            fn create (name: &'static str) -> Result<Self::DerivedObject, String> {
-                let c_string = CString::new(name).expect("CString::new failed");
+            fn create (name: &str) -> Result<Self::DerivedObject, ShogunError> {
+                let c_string = details::name_to_cstring(name)?;
                 let c_ptr = unsafe { shogun_sys::#create_name_ident(c_string.as_ptr()) };
-                unsafe {
-                    match c_ptr {
-                        shogun_sys::sgobject_result { return_code: shogun_sys::RETURN_CODE_SUCCESS,
-                                        result: shogun_sys::sgobject_result_ResultUnion { result: ptr } } => Ok(#name { ptr }),
-                        shogun_sys::sgobject_result { return_code: shogun_sys::RETURN_CODE_ERROR,
-                            result: shogun_sys::sgobject_result_ResultUnion { error: msg } } => {
-                            let c_error_str = CStr::from_ptr(msg);
-                            Err(format!("{}", c_error_str.to_str().expect("Failed to get error")))
-                        },
-                        _ => Err(format!("Unexpected return."))
-                    }
-                }
+                handle_sgobject_result::<#name>(&c_ptr)
+                    .map_err(|error| details::suggest_class(error, name, #name::available_classes))
+            }
+
+            fn deep_clone(&self) -> Result<Self::DerivedObject, ShogunError> {
+                let c_ptr = unsafe { shogun_sys::sgobject_clone(self.ptr) };
+                handle_sgobject_result::<#name>(&c_ptr)
+            }
+
+            fn from_json(json: &str) -> Result<Self::DerivedObject, ShogunError> {
+                let c_json = CString::new(json)
+                    .map_err(|_| ShogunError::InvalidInput("JSON contains a null byte".to_string()))?;
+                let c_ptr = unsafe { shogun_sys::sgobject_from_json(c_json.as_ptr()) };
+                details::expect_derived_type(handle_sgobject_result::<#name>(&c_ptr)?, shogun_sys::#sg_type_ident, stringify!(#name))
             }
 
-            fn to_string(&self) -> &str {
-                return details::sgobject_to_string(self.ptr);
+            fn load_as<P: AsRef<std::path::Path>>(path: P, format: SerializationFormat) -> Result<Self::DerivedObject, ShogunError> {
+                let c_path = details::path_to_cstring(path.as_ref())?;
+                let c_ptr = unsafe { shogun_sys::sgobject_load(c_path.as_ptr(), format.to_c()) };
+                details::expect_derived_type(handle_sgobject_result::<#name>(&c_ptr)?, shogun_sys::#sg_type_ident, stringify!(#name))
+            }
+
+            fn share(&self) -> Result<Self::DerivedObject, ShogunError> {
+                let c_ptr = unsafe { shogun_sys::sgobject_share(self.ptr) };
+                handle_sgobject_result::<#name>(&c_ptr)
+            }
+
+            fn to_string(&self) -> String {
+                details::sgobject_to_string(self.ptr)
             }
         }
 
         impl SGObjectPut for #name {
-            fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &'static str) -> Result<(), String> {
+            unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
                 unsafe {
-                    let c_string = CString::new(parameter_name).expect("CString::new failed");
+                    let c_string = details::name_to_cstring(parameter_name)?;
                     let type_erased_parameter = std::mem::transmute::<*mut shogun_sys::sgobject, *const std::ffi::c_void>(self.ptr);
                     details::handle_result(&shogun_sys::sgobject_put(obj, c_string.as_ptr(), type_erased_parameter, shogun_sys::TYPE_SGOBJECT))
                 }
             }
         }
 
+        impl SGObjectGet for #name {
+            fn type_name() -> &'static str {
+                stringify!(#name)
+            }
+            const IS_SGOBJECT: bool = true;
+            unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self> {
+                unsafe {
+                    if shogun_sys::get_cvisitor_type(c_visitor) != shogun_sys::TYPE_SGOBJECT {
+                        return None;
+                    }
+                    let obj = shogun_sys::get_cvisitor_pointer(c_visitor) as *mut shogun_sys::sgobject_t;
+                    if obj.is_null() || shogun_sys::sgobject_derived_type(obj) != shogun_sys::#sg_type_ident {
+                        return None;
+                    }
+                    let ptr = shogun_sys::release_cvisitor_pointer(c_visitor) as *mut shogun_sys::sgobject_t;
+                    Some(#name { ptr })
+                }
+            }
+        }
+
         impl SGObjectFromPtr for #name {
             type DerivedObject = #name;
-            fn from_ptr(ptr: *mut shogun_sys::sgobject) -> Self::DerivedObject {
+            unsafe fn from_ptr(ptr: *mut shogun_sys::sgobject) -> Self::DerivedObject {
                 #name { ptr }
             }
         }
 
+        // the C API only hands out shared_ptrs to the native object and
+        // locks the object graph for every operation, see the SGObject
+        // documentation
+        unsafe impl Send for #name {}
+        unsafe impl Sync for #name {}
+
         impl HasSGObjectPtr for #name {
             fn get_ptr(&self) -> *mut shogun_sys::sgobject {
                 self.ptr
@@ -89,6 +146,14 @@ pub fn derive(input: TokenStream) -> TokenStream {
             }
         }
 
+        /// Objects that fail to compare are treated as different
+        #[cfg(feature = "partial-eq")]
+        impl PartialEq for #name {
+            fn eq(&self, other: &Self) -> bool {
+                self.equals(other).unwrap_or(false)
+            }
+        }
+
         impl fmt::Display for #name {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "{}", SGObject::to_string(self))
@@ -119,6 +184,14 @@ fn from_sg_enum_to_rust_type(sg_enum: &str) -> String {
     result
 }
 
+/// Whether ndarray vectors and matrices of the type can be put and get
+fn has_array_type(type_enum: &str) -> bool {
+    match type_enum {
+        "INT32" | "INT64" | "FLOAT32" | "FLOAT64" => true,
+        _ => false,
+    }
+}
+
 fn from_primitive_type_to_rust_type(type_enum: &str) -> Option<String> {
     // unfortunately the type names are quite different in rust
     // so need to do some manual mapping
@@ -127,11 +200,364 @@ fn from_primitive_type_to_rust_type(type_enum: &str) -> Option<String> {
         "INT64" => Some("i64".to_string()),
         "FLOAT32" => Some("f32".to_string()),
         "FLOAT64" => Some("f64".to_string()),
+        "BOOL" => Some("bool".to_string()),
+        "UINT32" => Some("u32".to_string()),
+        "UINT64" => Some("u64".to_string()),
+        "CHAR" => Some("char".to_string()),
+        // enums are read as the name of their option
+        "STRING" | "ENUM" => Some("String".to_string()),
         "SGOBJECT" => None,
         _ => panic!(format!("Unknown type enum {}", type_enum)),
     }
 }
 
+/// Wrapper type names and their SG_TYPE enum values from the bindings
+fn sg_types() -> (Vec<Ident>, Vec<Ident>) {
+    let string = include_str!("bindings.rs");
+    let syntax = syn::parse_file(string).expect("Unable to parse file");
+    let sg_type_matcher = Regex::new(r"^SG_TYPE_SG_(.*)$").unwrap();
+
+    let mut sg_types_ident = Vec::new();
+    let mut sg_types_enum_ident = Vec::new();
+    for item in syntax.items {
+        if let Item::Const(item) = item {
+            let enum_value = &item.ident.to_string();
+            if let Some(captures) = sg_type_matcher.captures(enum_value) {
+                let type_name = from_sg_enum_to_rust_type(captures.get(1).unwrap().as_str());
+                sg_types_enum_ident.push(Ident::new(enum_value, item.ident.span()));
+                sg_types_ident.push(Ident::new(&type_name, item.ident.span()));
+            }
+        }
+    }
+    (sg_types_ident, sg_types_enum_ident)
+}
+
+#[proc_macro]
+pub fn any_sgobject(_input: TokenStream) -> TokenStream {
+    let (sg_types_ident, sg_types_enum_ident) = sg_types();
+
+    let result = quote! {
+        /// Any of the SGObject wrappers, for code that only knows the type
+        /// of an object at runtime
+        pub enum AnySGObject {
+            #(#sg_types_ident(#sg_types_ident),)*
+        }
+
+        impl AnySGObject {
+            /// Name of the wrapper type, which is the Shogun base class
+            pub fn base_type(&self) -> &'static str {
+                match self {
+                    #(AnySGObject::#sg_types_ident(_) => stringify!(#sg_types_ident),)*
+                }
+            }
+
+            /// Name of the Shogun class, e.g. GaussianKernel for a Kernel
+            pub fn class_name(&self) -> String {
+                details::sgobject_class_name(self.get_ptr())
+            }
+
+            /// Creates an object from the names of its wrapper type and class
+            pub fn create(base_type: &str, class_name: &str) -> Result<Self, ShogunError> {
+                match base_type {
+                    #(stringify!(#sg_types_ident) => Ok(AnySGObject::#sg_types_ident(#sg_types_ident::new(class_name)?)),)*
+                    _ => Err(ShogunError::InvalidInput(format!("Unknown base type {}", base_type))),
+                }
+            }
+
+            /// Reads a parameter of the object, see SGObject::get_any
+            pub fn get_any(&self, parameter_name: &str) -> Result<Box<dyn std::any::Any>, ShogunError> {
+                match self {
+                    #(AnySGObject::#sg_types_ident(obj) => obj.get_any(parameter_name),)*
+                }
+            }
+
+            /// The wrapper of the object, e.g. a Kernel, as returned by get_any
+            pub(crate) fn into_any(self) -> Box<dyn std::any::Any> {
+                match self {
+                    #(AnySGObject::#sg_types_ident(obj) => Box::new(obj),)*
+                }
+            }
+
+            /// Snapshot of a value returned by get_any if it is an object
+            #[cfg(feature = "serde")]
+            pub(crate) fn snapshot_of_any(value: &dyn std::any::Any) -> Option<Result<ParameterSnapshot, ShogunError>> {
+                #(
+                    if let Some(obj) = value.downcast_ref::<#sg_types_ident>() {
+                        return Some(ParameterSnapshot::new(obj));
+                    }
+                )*
+                None
+            }
+
+            /// Another handle to the native object of any object handle
+            pub(crate) fn share_of<T: HasSGObjectPtr + ?Sized>(obj: &T) -> Result<Self, ShogunError> {
+                handle_sgobject_result::<AnySGObject>(unsafe { &shogun_sys::sgobject_share(obj.get_ptr()) })
+            }
+
+            /// Name of the wrapper type of any object handle
+            pub(crate) fn base_type_of<T: HasSGObjectPtr>(obj: &T) -> &'static str {
+                match unsafe { shogun_sys::sgobject_derived_type(obj.get_ptr()) } {
+                    #(shogun_sys::#sg_types_enum_ident => stringify!(#sg_types_ident),)*
+                    _ => "SGObject",
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            pub fn snapshot(&self) -> Result<ParameterSnapshot, ShogunError> {
+                match self {
+                    #(AnySGObject::#sg_types_ident(obj) => ParameterSnapshot::new(obj),)*
+                }
+            }
+
+            /// Creates the object of a snapshot and puts its parameters
+            #[cfg(feature = "serde")]
+            pub fn restore(snapshot: &ParameterSnapshot) -> Result<Self, ShogunError> {
+                let mut obj = AnySGObject::create(&snapshot.base_type, &snapshot.class_name)?;
+                match &mut obj {
+                    #(AnySGObject::#sg_types_ident(inner) => snapshot.put_parameters(inner)?,)*
+                }
+                Ok(obj)
+            }
+        }
+
+        impl SGObjectFromPtr for AnySGObject {
+            type DerivedObject = AnySGObject;
+            unsafe fn from_ptr(ptr: *mut shogun_sys::sgobject) -> AnySGObject {
+                match unsafe { shogun_sys::sgobject_derived_type(ptr) } {
+                    #(shogun_sys::#sg_types_enum_ident => AnySGObject::#sg_types_ident(#sg_types_ident { ptr }),)*
+                    _ => panic!("Unknown SGObject base type"),
+                }
+            }
+        }
+
+        impl fmt::Debug for AnySGObject {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", self.base_type(), self.class_name())
+            }
+        }
+
+        impl HasSGObjectPtr for AnySGObject {
+            fn get_ptr(&self) -> *mut shogun_sys::sgobject {
+                match self {
+                    #(AnySGObject::#sg_types_ident(obj) => obj.get_ptr(),)*
+                }
+            }
+        }
+
+        impl SGObjectPut for AnySGObject {
+            unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+                match self {
+                    #(AnySGObject::#sg_types_ident(value) => value.sgobject_put(obj, parameter_name),)*
+                }
+            }
+        }
+
+        impl SGObjectGet for AnySGObject {
+            fn type_name() -> &'static str {
+                "AnySGObject"
+            }
+            const IS_SGOBJECT: bool = true;
+            unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self> {
+                unsafe {
+                    if shogun_sys::get_cvisitor_type(c_visitor) != shogun_sys::TYPE_SGOBJECT {
+                        return None;
+                    }
+                    let obj = shogun_sys::get_cvisitor_pointer(c_visitor) as *mut shogun_sys::sgobject_t;
+                    if obj.is_null() {
+                        return None;
+                    }
+                    match shogun_sys::sgobject_derived_type(obj) {
+                        #(shogun_sys::#sg_types_enum_ident => {
+                            let ptr = shogun_sys::release_cvisitor_pointer(c_visitor) as *mut shogun_sys::sgobject_t;
+                            Some(AnySGObject::#sg_types_ident(#sg_types_ident { ptr }))
+                        },)*
+                        _ => None,
+                    }
+                }
+            }
+        }
+
+        #(
+            impl From<#sg_types_ident> for AnySGObject {
+                fn from(obj: #sg_types_ident) -> Self {
+                    AnySGObject::#sg_types_ident(obj)
+                }
+            }
+
+            impl std::convert::TryFrom<AnySGObject> for #sg_types_ident {
+                type Error = ShogunError;
+                fn try_from(obj: AnySGObject) -> Result<Self, ShogunError> {
+                    match obj {
+                        AnySGObject::#sg_types_ident(obj) => Ok(obj),
+                        other => Err(ShogunError::TypeMismatch(format!("expected {}, got {}", stringify!(#sg_types_ident), other.base_type()))),
+                    }
+                }
+            }
+        )*
+
+        impl fmt::Display for AnySGObject {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    #(AnySGObject::#sg_types_ident(obj) => write!(f, "{}", obj),)*
+                }
+            }
+        }
+    };
+    result.into()
+}
+
+/// A class of wrappers.manifest with its parameters
+struct WrapperClass {
+    base_type: String,
+    class_name: String,
+    parameters: Vec<WrapperParameter>,
+}
+
+/// A parameter of a class in wrappers.manifest
+struct WrapperParameter {
+    name: String,
+    rust_type: String,
+    /// name of the getter, the setter adds a set_ prefix
+    rust_name: String,
+}
+
+fn parse_wrappers_manifest(manifest: &str) -> Vec<WrapperClass> {
+    let mut classes: Vec<WrapperClass> = Vec::new();
+    for line in manifest.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if line.starts_with(char::is_whitespace) {
+            if tokens.len() != 2 && tokens.len() != 3 {
+                panic!("Expected a name, a type and an optional Rust name in wrappers.manifest line \"{}\"", line);
+            }
+            classes.last_mut()
+                .unwrap_or_else(|| panic!("Parameter {} does not belong to a class", tokens[0]))
+                .parameters.push(WrapperParameter {
+                    name: tokens[0].to_string(),
+                    rust_type: tokens[1].to_string(),
+                    rust_name: tokens.get(2).map_or_else(|| tokens[0].to_lowercase(), |name| name.to_string()),
+                });
+        }
+        else {
+            if tokens.len() != 2 {
+                panic!("Expected two names in wrappers.manifest line \"{}\"", line);
+            }
+            classes.push(WrapperClass {
+                base_type: tokens[0].to_string(),
+                class_name: tokens[1].to_string(),
+                parameters: Vec::new(),
+            });
+        }
+    }
+    classes
+}
+
+#[proc_macro]
+pub fn typed_wrappers(_input: TokenStream) -> TokenStream {
+    let manifest = include_str!("wrappers.manifest");
+    let span = proc_macro::Span::call_site().into();
+
+    let wrappers = parse_wrappers_manifest(manifest).into_iter().map(|class| {
+        let name = Ident::new(&class.class_name, span);
+        let base = Ident::new(&class.base_type, span);
+        let class_name = &class.class_name;
+        let doc = format!("Typed handle for Shogun's {}, which derefs to a {}", class.class_name, class.base_type);
+
+        let accessors = class.parameters.iter().map(|parameter| {
+            let parameter_name = &parameter.name;
+            let rust_type = &parameter.rust_type;
+            let getter = Ident::new(&parameter.rust_name, span);
+            let setter = Ident::new(&format!("set_{}", parameter.rust_name), span);
+            let value_type: syn::Type = syn::parse_str(rust_type)
+                .unwrap_or_else(|_| panic!("Invalid type {} of parameter {}", rust_type, parameter_name));
+            // primitive values are passed by value, objects by reference
+            let setter_type = if rust_type.starts_with(char::is_lowercase) {
+                quote! { #value_type }
+            } else {
+                quote! { &#value_type }
+            };
+            let value_ref = if rust_type.starts_with(char::is_lowercase) {
+                quote! { &value }
+            } else {
+                quote! { value }
+            };
+            quote! {
+                pub fn #getter(&self) -> Result<#value_type, ShogunError> {
+                    self.0.get::<#value_type>(#parameter_name)
+                }
+
+                pub fn #setter(&mut self, value: #setter_type) -> Result<(), ShogunError> {
+                    self.0.put(#parameter_name, #value_ref)
+                }
+            }
+        });
+
+        quote! {
+            #[doc = #doc]
+            pub struct #name(#base);
+
+            impl #name {
+                pub fn new() -> Result<Self, ShogunError> {
+                    Ok(#name(#base::new(#class_name)?))
+                }
+
+                #(#accessors)*
+            }
+
+            impl Deref for #name {
+                type Target = #base;
+                fn deref(&self) -> &#base {
+                    &self.0
+                }
+            }
+
+            impl std::ops::DerefMut for #name {
+                fn deref_mut(&mut self) -> &mut #base {
+                    &mut self.0
+                }
+            }
+
+            impl From<#name> for #base {
+                fn from(obj: #name) -> #base {
+                    obj.0
+                }
+            }
+
+            /// Fails if the object is an instance of another class
+            impl std::convert::TryFrom<#base> for #name {
+                type Error = ShogunError;
+                fn try_from(obj: #base) -> Result<Self, ShogunError> {
+                    let obj_class = obj.class_name();
+                    if obj_class == #class_name {
+                        Ok(#name(obj))
+                    } else {
+                        Err(ShogunError::TypeMismatch(format!("expected {}, got {}", #class_name, obj_class)))
+                    }
+                }
+            }
+
+            impl SGObjectPut for #name {
+                unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+                    self.0.sgobject_put(obj, parameter_name)
+                }
+            }
+
+            impl fmt::Display for #name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", self.0)
+                }
+            }
+        }
+    });
+
+    let result = quote! {
+        #(#wrappers)*
+    };
+    result.into()
+}
+
 #[proc_macro]
 pub fn getter_reflection(_input: TokenStream) -> TokenStream {
     
@@ -141,6 +567,8 @@ pub fn getter_reflection(_input: TokenStream) -> TokenStream {
     let mut sg_primitive_types_ident = Vec::new();
     let mut sg_types_ident = Vec::new();
     let mut sg_primitive_types_enum_ident = Vec::new();
+    let mut sg_array_types_ident = Vec::new();
+    let mut sg_array_types_enum_ident = Vec::new();
     let mut sg_types_enum_ident = Vec::new();
 
     let sg_type_matcher = Regex::new(r"^SG_TYPE_SG_(.*)$").unwrap();
@@ -164,6 +592,10 @@ pub fn getter_reflection(_input: TokenStream) -> TokenStream {
                         Some(x) => {
                             sg_primitive_types_enum_ident.push(Ident::new(enum_value, item.ident.span()));
                             sg_primitive_types_ident.push(Ident::new(&x, item.ident.span()));
+                            if has_array_type(&raw_type_name) {
+                                sg_array_types_enum_ident.push(Ident::new(enum_value, item.ident.span()));
+                                sg_array_types_ident.push(Ident::new(&x, item.ident.span()));
+                            }
                         },
                         _ => (),
                     }
@@ -174,27 +606,77 @@ pub fn getter_reflection(_input: TokenStream) -> TokenStream {
     }
 
     let result = quote! {
-        /// Parameter getter  
-        fn get(&self, parameter_name: &'static str) -> Result<Box<dyn std::any::Any>, String> {
+        /// Typed parameter getter, fails if the parameter does not hold a T
+        fn get<T>(&self, parameter_name: &str) -> Result<T, ShogunError>
+        where T: SGObjectGet {
             unsafe {
-                let c_string = CString::new(parameter_name).expect("CString::new failed");
-                let c_visitor = shogun_sys::sgobject_get(self.get_ptr(), c_string.as_ptr());
+                let c_string = details::name_to_cstring(parameter_name)?;
+                let c_visitor = details::handle_cvisitor_result(&shogun_sys::sgobject_get(self.get_ptr(), c_string.as_ptr()))?;
+                let result = if shogun_sys::get_cvisitor_pointer(c_visitor).is_null() {
+                    Err(details::null_value_error(c_visitor, parameter_name))
+                } else if let Some(value) = T::from_cvisitor(c_visitor) {
+                    Ok(value)
+                } else {
+                    let actual_type = match (shogun_sys::get_cvisitor_container(c_visitor), shogun_sys::get_cvisitor_type(c_visitor)) {
+                        #((shogun_sys::CONTAINER_TYPE_CT_SCALAR, shogun_sys::#sg_primitive_types_enum_ident) => stringify!(#sg_primitive_types_ident).to_string(),)*
+                        #((shogun_sys::CONTAINER_TYPE_CT_VECTOR, shogun_sys::#sg_array_types_enum_ident) => <ndarray::Array1<#sg_array_types_ident> as SGObjectGet>::type_name().to_string(),)*
+                        #((shogun_sys::CONTAINER_TYPE_CT_MATRIX, shogun_sys::#sg_array_types_enum_ident) => <ndarray::Array2<#sg_array_types_ident> as SGObjectGet>::type_name().to_string(),)*
+                        (_, shogun_sys::TYPE_SGOBJECT) => {
+                            let obj = shogun_sys::get_cvisitor_pointer(c_visitor) as *mut shogun_sys::sgobject_t;
+                            match shogun_sys::sgobject_derived_type(obj) {
+                                #(shogun_sys::#sg_types_enum_ident => stringify!(#sg_types_ident).to_string(),)*
+                                _ => "SGObject".to_string(),
+                            }
+                        },
+                        _ => {
+                            let c_typename = CStr::from_ptr(shogun_sys::get_cvisitor_typename(c_visitor));
+                            c_typename.to_str().expect("Failed to get typename").to_string()
+                        },
+                    };
+                    Err(ShogunError::TypeMismatch(format!("parameter {} expected {}, got {}", parameter_name, T::type_name(), actual_type)))
+                };
+                shogun_sys::destroy_cvisitor(c_visitor);
+                result
+            }
+        }
+
+        /// Parameter getter for dynamically typed code
+        fn get_any(&self, parameter_name: &str) -> Result<Box<dyn std::any::Any>, ShogunError> {
+            unsafe {
+                let c_string = details::name_to_cstring(parameter_name)?;
+                let c_visitor = details::handle_cvisitor_result(&shogun_sys::sgobject_get(self.get_ptr(), c_string.as_ptr()))?;
+                if shogun_sys::get_cvisitor_pointer(c_visitor).is_null() {
+                    let error = details::null_value_error(c_visitor, parameter_name);
+                    shogun_sys::destroy_cvisitor(c_visitor);
+                    return Err(error);
+                }
                 let c_visitor_type = shogun_sys::get_cvisitor_type(c_visitor);
-                match c_visitor_type {
-                    #(shogun_sys::#sg_primitive_types_enum_ident => Ok(Box::from_raw(shogun_sys::get_cvisitor_pointer(c_visitor) as *mut #sg_primitive_types_ident)),)*
-                    shogun_sys::TYPE_SGOBJECT => {
-                        let obj = shogun_sys::get_cvisitor_pointer(c_visitor) as *mut shogun_sys::sgobject_t ;
-                        let obj_type = shogun_sys::sgobject_derived_type(obj);
-                        match obj_type {
-                            #(shogun_sys::#sg_types_enum_ident => Ok(Box::new(#sg_types_ident {ptr: obj })),)*
-                            _ => Err(format!("Cannot handle type")),
+                let result: Result<Box<dyn std::any::Any>, ShogunError> = match (shogun_sys::get_cvisitor_container(c_visitor), c_visitor_type) {
+                    #((shogun_sys::CONTAINER_TYPE_CT_SCALAR, shogun_sys::#sg_primitive_types_enum_ident) => {
+                        let value = <#sg_primitive_types_ident as SGObjectGet>::from_cvisitor(c_visitor).expect("Failed to read scalar");
+                        Ok(Box::new(value))
+                    },)*
+                    #((shogun_sys::CONTAINER_TYPE_CT_VECTOR, shogun_sys::#sg_array_types_enum_ident) => {
+                        let vector = <ndarray::Array1<#sg_array_types_ident> as SGObjectGet>::from_cvisitor(c_visitor).expect("Failed to read vector");
+                        Ok(Box::new(vector))
+                    },)*
+                    #((shogun_sys::CONTAINER_TYPE_CT_MATRIX, shogun_sys::#sg_array_types_enum_ident) => {
+                        let matrix = <ndarray::Array2<#sg_array_types_ident> as SGObjectGet>::from_cvisitor(c_visitor).expect("Failed to read matrix");
+                        Ok(Box::new(matrix))
+                    },)*
+                    (_, shogun_sys::TYPE_SGOBJECT) => {
+                        match <AnySGObject as SGObjectGet>::from_cvisitor(c_visitor) {
+                            Some(obj) => Ok(obj.into_any()),
+                            None => Err(ShogunError::TypeMismatch("Cannot handle type".to_string())),
                         }
                     },
                     _ => {
                         let c_typename = CStr::from_ptr(shogun_sys::get_cvisitor_typename(c_visitor));
-                        Err(format!("Cannot handle type {}", c_typename.to_str().expect("Failed to get typename")))
+                        Err(ShogunError::TypeMismatch(format!("Cannot handle type {}", c_typename.to_str().expect("Failed to get typename"))))
                     },
-                }
+                };
+                shogun_sys::destroy_cvisitor(c_visitor);
+                result
             }
         }
     };
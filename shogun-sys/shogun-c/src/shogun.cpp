@@ -1,26 +1,177 @@
 #include "shogun.hpp"
+#include <shogun/base/class_list.h>
+#include <shogun/features/CombinedFeatures.h>
+#include <shogun/features/DenseFeatures.h>
+#include <shogun/features/DotFeatures.h>
+#include <shogun/features/SparseFeatures.h>
+#include <shogun/features/StringFeatures.h>
+#include <shogun/io/serialization/JsonDeserializer.h>
+#include <shogun/io/serialization/JsonSerializer.h>
+#include <shogun/io/stream/ByteArrayInputStream.h>
+#include <shogun/io/stream/ByteArrayOutputStream.h>
+#if __has_include(<shogun/io/serialization/BitserySerializer.h>)
+#include <shogun/io/serialization/BitseryDeserializer.h>
+#include <shogun/io/serialization/BitserySerializer.h>
+#define SHIM_HAS_BINARY_SERIALIZATION
+#endif
+#include <shogun/kernel/CombinedKernel.h>
 #include <shogun/util/factory.h>
 
+#include <algorithm>
+#include <array>
+#include <atomic>
+#include <cstddef>
+#include <cstdint>
+#include <fstream>
+#include <iterator>
+#include <limits>
+#include <memory>
+#include <mutex>
+#include <optional>
+#include <shared_mutex>
+#include <unordered_map>
+#include <unordered_set>
+
 using namespace shogun;
 
+/** Number of objects and strings handed out through the C API that
+ * have not been released yet.
+ */
+std::atomic<int64_t> live_allocations{0};
+
+/** Member that keeps live_allocations in sync with the lifetime of
+ * the object that holds it.
+ */
+struct LiveAllocation {
+	LiveAllocation() { ++live_allocations; }
+	LiveAllocation(const LiveAllocation&) { ++live_allocations; }
+	~LiveAllocation() { --live_allocations; }
+};
+
+/** Copies str into a buffer that the caller releases with free_string */
+const char* copy_c_string(const std::string& str) {
+	auto* result = (char*)malloc(str.size() + 1);
+	strcpy(result, str.c_str());
+	++live_allocations;
+	return result;
+}
+
 // taken from cpp reference
 template<class... Ts> struct overloaded : Ts... { using Ts::operator()...; };
 template<class... Ts> overloaded(Ts...) -> overloaded<Ts...>;
 
 struct version {
 	Version* obj;
+	LiveAllocation m_live;
 };
 
 struct C_Visitor {
 	std::pair<TYPE, std::string_view> m_type;
-	void* m_value;
+	void* m_value = nullptr;
+	// releases m_value unless the caller took ownership of it
+	void (*m_deleter)(void*) = nullptr;
+	CONTAINER_TYPE m_container = CONTAINER_TYPE::CT_SCALAR;
+	// vectors only use m_rows
+	uint32_t m_rows = 0;
+	uint32_t m_cols = 0;
+	LiveAllocation m_live;
 };
 
 struct Put_Visitor {
 	const void* m_value;
 	const TYPE type;
+	const CONTAINER_TYPE container = CONTAINER_TYPE::CT_SCALAR;
+};
+
+/** Collects the objects that parameters point to */
+struct Graph_Visitor {
+	std::vector<std::shared_ptr<SGObject>> children;
+};
+
+struct parameter_list {
+	struct entry {
+		std::string name;
+		std::string type_name;
+		std::string description;
+		uint32_t properties;
+	};
+
+	std::vector<entry> entries;
+	LiveAllocation m_live;
+};
+
+struct string_list {
+	std::vector<std::string> strings;
+	LiveAllocation m_live;
+};
+
+/** Copy of a SGSparseMatrix in compressed form, with one sample per
+ * outer dimension and sorted feature indices.
+ */
+struct sparse_matrix {
+	TYPE type;
+	uint32_t num_features;
+	uint32_t num_vectors;
+	std::vector<uint64_t> indptr;
+	std::vector<uint64_t> indices;
+	// data of type `type`
+	std::vector<char> data;
+	LiveAllocation m_live;
+};
+
+/** Exception thrown by the shim itself, carries the error category
+ * that is reported back to the caller.
+ */
+struct ShimException: public std::runtime_error {
+	ShimException(ERROR_CODE code_, const std::string& msg): std::runtime_error(msg), code(code_) {}
+
+	const ERROR_CODE code;
 };
 
+ERROR_CODE error_code_from_exception(const std::exception& e) {
+	if (auto* shim_exception = dynamic_cast<const ShimException*>(&e))
+		return shim_exception->code;
+	return ERROR_CODE::NATIVE_EXCEPTION;
+}
+
+/** The error message is copied, so it outlives the exception it may come
+ * from, and has to be released by the caller with free_string.
+ */
+template <typename ResultType=sgobject_result>
+ResultType error_result(ERROR_CODE code, const char* msg) {
+	ResultType result;
+	result.return_code = RETURN_CODE::ERROR;
+	result.result.error = copy_c_string(msg);
+	result.error_code = code;
+	return result;
+}
+
+template <>
+Result error_result<Result>(ERROR_CODE code, const char* msg) {
+	return {RETURN_CODE::ERROR, copy_c_string(msg), code};
+}
+
+template <typename ResultType=sgobject_result>
+ResultType error_result(const std::exception& e) {
+	return error_result<ResultType>(error_code_from_exception(e), e.what());
+}
+
+/** Runs func and reports any exception it throws as an error result,
+ * since exceptions must not propagate through the C API.
+ */
+template <typename ResultType=sgobject_result, typename Function>
+ResultType exception_firewall(Function&& func) {
+	try {
+		return func();
+	}
+	catch (const std::exception& e) {
+		return error_result<ResultType>(e);
+	}
+	catch (...) {
+		return error_result<ResultType>(ERROR_CODE::NATIVE_EXCEPTION, "Unknown exception");
+	}
+}
+
 template <typename>
 struct get_type {};
 
@@ -36,6 +187,11 @@ DEFINE_TYPE(int32_t, INT32)
 DEFINE_TYPE(int64_t, INT64)
 DEFINE_TYPE(float32_t, FLOAT32)
 DEFINE_TYPE(float64_t, FLOAT64)
+DEFINE_TYPE(bool, BOOL)
+DEFINE_TYPE(uint32_t, UINT32)
+DEFINE_TYPE(uint64_t, UINT64)
+DEFINE_TYPE(char, CHAR)
+DEFINE_TYPE(std::string, STRING)
 DEFINE_TYPE(std::shared_ptr<SGObject>, SGOBJECT)
 
 #undef DEFINE_TYPE
@@ -51,6 +207,143 @@ class VisitorRegister {
 		static VisitorRegister* instance();
 };
 
+/** Locks all the native objects reachable from a set of objects.
+ *
+ * Objects reach each other through their parameters (e.g. the kernel of a
+ * machine or the features of a kernel), and operations on one object may
+ * read or modify the objects it reaches. So an operation locks every object
+ * reachable from its arguments, and operations on unrelated object graphs
+ * run concurrently. Objects map to a fixed set of readers-writer locks by
+ * address, so unrelated objects only wait for each other if their locks
+ * collide.
+ *
+ * The locks are taken while walking the graph, so that the parameters of an
+ * object are only read while it is locked. If a lock is busy, all locks are
+ * released and the walk starts over by waiting for that lock, which avoids
+ * deadlocks between operations that walk overlapping graphs.
+ */
+class GraphLock {
+public:
+	enum class Mode {SHARED, EXCLUSIVE};
+
+	/** Locks the objects reachable from exclusive_roots exclusively and the
+	 * remaining objects reachable from shared_roots shared
+	 */
+	GraphLock(const std::vector<std::shared_ptr<SGObject>>& exclusive_roots, const std::vector<std::shared_ptr<SGObject>>& shared_roots) {
+		try {
+			auto busy = lock_reachable(exclusive_roots, shared_roots, std::nullopt);
+			while (busy)
+				busy = lock_reachable(exclusive_roots, shared_roots, busy);
+		}
+		catch (...) {
+			unlock_all();
+			throw;
+		}
+	}
+
+	GraphLock(const GraphLock&) = delete;
+	GraphLock& operator=(const GraphLock&) = delete;
+
+	~GraphLock() {
+		unlock_all();
+	}
+
+private:
+	using Stripe = std::pair<size_t, Mode>;
+
+	static constexpr size_t num_stripes = 251;
+
+	static std::array<std::shared_mutex, num_stripes>& stripes() {
+		static std::array<std::shared_mutex, num_stripes> mutexes;
+		return mutexes;
+	}
+
+	static size_t stripe_of(const SGObject* obj) {
+		return (reinterpret_cast<uintptr_t>(obj) / alignof(std::max_align_t)) % num_stripes;
+	}
+
+	static std::vector<std::shared_ptr<SGObject>> children_of(const SGObject* obj) {
+		Graph_Visitor visitor;
+		for (const auto& [name, param]: obj->get_params()) {
+			// parameters of types without a registered visitor cannot
+			// point to objects
+			try {
+				param->get_value().visit_with(&visitor);
+			}
+			catch (...) {}
+		}
+		return visitor.children;
+	}
+
+	/** Locks the objects reachable from the roots, first waiting for the
+	 * lock busy if the previous attempt failed on it. Returns the lock that
+	 * was busy if the attempt failed, with all locks released again.
+	 */
+	std::optional<Stripe> lock_reachable(
+		const std::vector<std::shared_ptr<SGObject>>& exclusive_roots,
+		const std::vector<std::shared_ptr<SGObject>>& shared_roots,
+		std::optional<Stripe> busy) {
+		if (busy)
+			lock(*busy);
+		// exclusive objects are visited first, so objects reachable from
+		// both kinds of roots are locked exclusively
+		std::unordered_set<const SGObject*> visited;
+		for (const auto& [roots, mode]: {std::pair{&exclusive_roots, Mode::EXCLUSIVE}, std::pair{&shared_roots, Mode::SHARED}}) {
+			auto pending = *roots;
+			while (!pending.empty()) {
+				const auto obj = pending.back();
+				pending.pop_back();
+				if (!obj || !visited.insert(obj.get()).second)
+					continue;
+				const auto stripe = stripe_of(obj.get());
+				const auto held = std::find_if(m_locked.begin(), m_locked.end(),
+					[&](const Stripe& locked) { return locked.first == stripe; });
+				if (held != m_locked.end() && (held->second == Mode::EXCLUSIVE || mode == Mode::SHARED)) {
+					// already locked strongly enough
+				}
+				else if (held != m_locked.end() || !try_lock({stripe, mode})) {
+					// a stripe locked shared cannot be upgraded, so the next
+					// attempt starts with it exclusively
+					unlock_all();
+					return Stripe{stripe, mode};
+				}
+				for (auto&& child: children_of(obj.get()))
+					pending.push_back(std::move(child));
+			}
+		}
+		return std::nullopt;
+	}
+
+	void lock(const Stripe& stripe) {
+		if (stripe.second == Mode::SHARED)
+			stripes()[stripe.first].lock_shared();
+		else
+			stripes()[stripe.first].lock();
+		m_locked.push_back(stripe);
+	}
+
+	bool try_lock(const Stripe& stripe) {
+		const auto locked = stripe.second == Mode::SHARED
+			? stripes()[stripe.first].try_lock_shared()
+			: stripes()[stripe.first].try_lock();
+		if (locked)
+			m_locked.push_back(stripe);
+		return locked;
+	}
+
+	void unlock_all() {
+		for (const auto& [stripe, mode]: m_locked) {
+			if (mode == Mode::SHARED)
+				stripes()[stripe].unlock_shared();
+			else
+				stripes()[stripe].unlock();
+		}
+		m_locked.clear();
+	}
+
+	std::vector<Stripe> m_locked;
+};
+
 struct sgobject {
 	std::variant<std::shared_ptr<Machine>, 
 			     std::shared_ptr<Kernel>, 
@@ -60,6 +353,7 @@ struct sgobject {
 				 std::shared_ptr<CombinationRule>,
 				 std::shared_ptr<Labels>,
 				 std::shared_ptr<Evaluation>> ptr;
+	LiveAllocation m_live;
 
 	template <typename T, std::enable_if_t<is_sg_base<T>::value>* = nullptr>
 	sgobject(const std::shared_ptr<T>& ptr_): ptr(ptr_) {
@@ -73,6 +367,35 @@ struct sgobject {
 
 	~sgobject() = default;
 
+	/** The object as its Shogun base class */
+	std::shared_ptr<SGObject> base() const {
+		return std::visit([](auto&& obj) -> std::shared_ptr<SGObject> { return obj; }, ptr);
+	}
+
+	/** Shared access to this object, the objects in others and everything
+	 * they reach, for operations that only read them
+	 */
+	GraphLock read_lock(std::initializer_list<const sgobject*> others = {}) const {
+		return GraphLock({}, graph_roots(this, others));
+	}
+
+	/** Exclusive access to this object, the objects in others and
+	 * everything they reach, for operations that may modify any of them.
+	 * The objects in read_only and what only they reach are locked shared.
+	 */
+	GraphLock write_lock(std::initializer_list<const sgobject*> others = {}, std::initializer_list<const sgobject*> read_only = {}) const {
+		return GraphLock(graph_roots(this, others), graph_roots(nullptr, read_only));
+	}
+
+	static std::vector<std::shared_ptr<SGObject>> graph_roots(const sgobject* self, std::initializer_list<const sgobject*> others) {
+		std::vector<std::shared_ptr<SGObject>> roots;
+		if (self)
+			roots.push_back(self->base());
+		for (const auto* other: others)
+			roots.push_back(other->base());
+		return roots;
+	}
+
 	std::string get_name() const {
 		return std::visit([](auto&& obj) {
 			return obj->get_name();
@@ -92,20 +415,70 @@ struct sgobject {
 		}, ptr);
 	}
 
+	auto get_params() const {
+		return std::visit([](auto&& arg){return arg->get_params();}, ptr);
+	}
+
+	/** Options of the enum parameter name, or nullptr if it is not an enum */
+	const std::unordered_map<std::string, machine_int_t>* enum_options(const char* name) const {
+		const auto& enum_map = std::visit([](auto&& obj) -> decltype(auto) {
+			return obj->get_string_to_enum_map();
+		}, ptr);
+		const auto options = enum_map.find(std::string(name));
+		if (options != enum_map.end())
+			return &options->second;
+		return nullptr;
+	}
+
+	/** Sets an enum parameter from the name of one of its options */
+	void put_enum(const char* name, const char* option) {
+		const auto* options = enum_options(name);
+		if (options->find(std::string(option)) == options->end()) {
+			std::string msg = "Unknown option " + std::string(option) + " for parameter " + name + ", expected one of:";
+			for (const auto& [option_name, _]: *options)
+				msg += " " + option_name;
+			throw ShimException(ERROR_CODE::INVALID_INPUT, msg);
+		}
+		// Shogun maps the option name to the enum value
+		std::visit([&](auto&& obj) {
+			obj->put(std::string(name), std::string(option));
+		}, ptr);
+	}
+
+	/** Name of the option the enum parameter name is set to */
+	std::string get_enum(const char* name) const {
+		const auto value = std::visit([&](auto&& obj) {
+			return obj->template get<machine_int_t>(std::string(name));
+		}, ptr);
+		for (const auto& [option_name, option_value]: *enum_options(name)) {
+			if (option_value == value)
+				return option_name;
+		}
+		throw ShimException(ERROR_CODE::INVALID_INPUT,
+			"Parameter " + std::string(name) + " is set to an unnamed option");
+	}
+
 	Any get_parameter(const char* name) const {
-		const auto params = std::visit([&name](auto&& arg){return arg->get_params();}, ptr);
+		const auto params = get_params();
 		const auto param = params.find(std::string(name));
 		if (param != params.end())
 			return param->second->get_value();
-		else
-			error("Could not find parameter {}::{}", get_name(), name);
+		throw ShimException(ERROR_CODE::PARAMETER_NOT_FOUND,
+			"Could not find parameter " + get_name() + "::" + name);
 	}
 
 	const char* to_string() const {
-		auto repr = std::visit([](auto&& arg) {return arg->to_string();}, ptr);
-		auto* result = (char*)malloc(sizeof(char*) * repr.size() + 1);
-		strcpy(result, repr.c_str());
-		return result;
+		try {
+			auto lock = read_lock();
+			auto repr = std::visit([](auto&& arg) {return arg->to_string();}, ptr);
+			return copy_c_string(repr);
+		}
+		catch (const std::exception& e) {
+			return copy_c_string(std::string("Failed to get string representation: ") + e.what());
+		}
+		catch (...) {
+			return copy_c_string("Failed to get string representation");
+		}
 	}
 };
 
@@ -114,6 +487,11 @@ VisitorRegister::VisitorRegister () {
 	register_visitor<float64_t>();
 	register_visitor<int32_t>();
 	register_visitor<int64_t>();
+	register_visitor<bool>();
+	register_visitor<uint32_t>();
+	register_visitor<uint64_t>();
+	register_visitor<char>();
+	register_visitor<std::string>();
 	register_visitor<Kernel>();
 	register_visitor<Machine>();
 	register_visitor<Distance>();
@@ -138,6 +516,34 @@ bool internal_type_promotions_compare_types(TYPE rhs, T* val_lhs, const void* va
 			return false;
 		return true;
 	}
+	// integer literals are i32 in Rust, so they are accepted for
+	// unsigned parameters as long as they are not negative
+	if constexpr (std::is_same_v<T, uint32_t> || std::is_same_v<T, uint64_t>)
+	{
+		// u64 values do not fit in an int64_t, so they are only narrowed
+		if (rhs == UINT64) {
+			const auto value = *static_cast<const uint64_t*>(val_rhs);
+			if (value > std::numeric_limits<T>::max())
+				throw ShimException(ERROR_CODE::INVALID_INPUT, "Value is too large for the unsigned parameter");
+			*val_lhs = static_cast<T>(value);
+			return true;
+		}
+		int64_t value;
+		if (rhs == INT32)
+			value = *static_cast<const int32_t*>(val_rhs);
+		else if (rhs == INT64)
+			value = *static_cast<const int64_t*>(val_rhs);
+		else if (rhs == UINT32)
+			value = *static_cast<const uint32_t*>(val_rhs);
+		else
+			return false;
+		if (value < 0)
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Cannot put a negative value in an unsigned parameter");
+		if (static_cast<uint64_t>(value) > std::numeric_limits<T>::max())
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Value is too large for the unsigned parameter");
+		*val_lhs = value;
+		return true;
+	}
 	return false;
 }
 
@@ -148,29 +554,45 @@ void VisitorRegister::register_visitor() {
 
 	Any::register_visitor<RegisterType, C_Visitor>(
 		[](RegisterType* val, C_Visitor* visitor) {
-			auto* result = new ReturnType;
-			*result = *val;
 			visitor->m_type = get_type<ReturnType>::type;
-			if constexpr (is_sg_base<T>::value)
-				visitor->m_value = (void*)new sgobject(*val);
-			else
-				visitor->m_value = (void*)result;
+			if constexpr (is_sg_base<T>::value) {
+				// unset objects keep m_value null, so they are reported as
+				// errors instead of handles to nothing
+				if (*val) {
+					visitor->m_value = (void*)new sgobject(*val);
+					visitor->m_deleter = [](void* value) { delete static_cast<sgobject*>(value); };
+				}
+			}
+			else if constexpr (std::is_same_v<T, std::string>) {
+				visitor->m_value = (void*)copy_c_string(*val);
+				visitor->m_deleter = [](void* value) { free_string(static_cast<const char*>(value)); };
+			}
+			else {
+				visitor->m_value = (void*)new ReturnType(*val);
+				visitor->m_deleter = [](void* value) { delete static_cast<ReturnType*>(value); };
+			}
 		}
 	);
 	Any::register_visitor<RegisterType, Put_Visitor>(
 		[](RegisterType* val, Put_Visitor* visitor) {
+			if (visitor->container != CONTAINER_TYPE::CT_SCALAR)
+				throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Expected a scalar value");
 			if (!internal_type_promotions_compare_types(
 				visitor->type, val, visitor->m_value)) {
 				// if the types were not casted internally check if types match exactly
 				if (get_type<ReturnType>::type.first != visitor->type) {
-					error("Type mismatch");
+					throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Type mismatch");
 				}
 				if constexpr (is_sg_base<T>::value) {
 					auto obj = static_cast<const sgobject_t*>(visitor->m_value);
 					if (std::holds_alternative<RegisterType>(obj->ptr))
 						*val = std::get<RegisterType>(obj->ptr);
 					else
-						error("SGObject type mismatch");
+						throw ShimException(ERROR_CODE::TYPE_MISMATCH, "SGObject type mismatch");
+				}
+				else if constexpr (std::is_same_v<T, std::string>) {
+					// strings are passed as null terminated char arrays
+					*val = std::string(static_cast<const char*>(visitor->m_value));
 				}
 				else {
 					// if we got here types match exactly so can static_cast
@@ -180,6 +602,25 @@ void VisitorRegister::register_visitor() {
 		}
 	);
 
+	// parameters that point to objects link them into one object graph,
+	// see GraphLock
+	if constexpr (is_sg_base<T>::value) {
+		Any::register_visitor<RegisterType, Graph_Visitor>(
+			[](RegisterType* val, Graph_Visitor* visitor) {
+				if (*val)
+					visitor->children.push_back(*val);
+			}
+		);
+		Any::register_visitor<std::vector<RegisterType>, Graph_Visitor>(
+			[](std::vector<RegisterType>* val, Graph_Visitor* visitor) {
+				for (const auto& obj: *val) {
+					if (obj)
+						visitor->children.push_back(obj);
+				}
+			}
+		);
+	}
+
 	// automatically registers arithmetic types' corresponding SGMatrix and SGVector
 	if constexpr (std::is_arithmetic_v<RegisterType>)
 	{
@@ -188,15 +629,46 @@ void VisitorRegister::register_visitor() {
 
 		Any::register_visitor<VectorType, Put_Visitor>( 
 			[](VectorType* val, Put_Visitor* visitor) {
+				if (visitor->container != CONTAINER_TYPE::CT_VECTOR || get_type<RegisterType>::type.first != visitor->type)
+					throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Vector type mismatch");
 				*val = *static_cast<const VectorType*>(visitor->m_value);
 			}
 		);
 
 		Any::register_visitor<MatrixType, Put_Visitor>( 
 			[](MatrixType* val, Put_Visitor* visitor) {
+				if (visitor->container != CONTAINER_TYPE::CT_MATRIX || get_type<RegisterType>::type.first != visitor->type)
+					throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Matrix type mismatch");
 				*val = *static_cast<const MatrixType*>(visitor->m_value);
 			}
 		);
+
+		Any::register_visitor<VectorType, C_Visitor>(
+			[](VectorType* val, C_Visitor* visitor) {
+				auto* result = new RegisterType[val->vlen];
+				sg_memcpy(result, val->vector, val->vlen * sizeof(RegisterType));
+				visitor->m_type = get_type<RegisterType>::type;
+				visitor->m_container = CONTAINER_TYPE::CT_VECTOR;
+				visitor->m_rows = val->vlen;
+				visitor->m_value = (void*)result;
+				visitor->m_deleter = [](void* value) { delete[] static_cast<RegisterType*>(value); };
+			}
+		);
+
+		// SGMatrix data is column-major
+		Any::register_visitor<MatrixType, C_Visitor>(
+			[](MatrixType* val, C_Visitor* visitor) {
+				const auto size = val->num_rows * val->num_cols;
+				auto* result = new RegisterType[size];
+				sg_memcpy(result, val->matrix, size * sizeof(RegisterType));
+				visitor->m_type = get_type<RegisterType>::type;
+				visitor->m_container = CONTAINER_TYPE::CT_MATRIX;
+				visitor->m_rows = val->num_rows;
+				visitor->m_cols = val->num_cols;
+				visitor->m_value = (void*)result;
+				visitor->m_deleter = [](void* value) { delete[] static_cast<RegisterType*>(value); };
+			}
+		);
 	}
 }
 
@@ -206,19 +678,27 @@ VisitorRegister* VisitorRegister::instance() {
 }
 
 version_t* create_version() {
-	auto* ptr = (version_t*)malloc(sizeof(version_t));
-	ptr->obj = new Version();
-	return ptr;
+	try {
+		auto ptr = std::make_unique<version_t>();
+		ptr->obj = new Version();
+		return ptr.release();
+	}
+	catch (...) {
+		return nullptr;
+	}
 }
 
-void set_parallel_threads(int32_t n_threads) {
-	env()->set_num_threads(n_threads);
+Result set_parallel_threads(int32_t n_threads) {
+	return exception_firewall<Result>([&]() -> Result {
+		env()->set_num_threads(n_threads);
+		return {RETURN_CODE::SUCCESS, nullptr};
+	});
 }
 
 void destroy_version(version_t* ptr) {
 	if (ptr) {
 		delete ptr->obj;
-		free(ptr);
+		delete ptr;
 	}
 }
 
@@ -226,51 +706,97 @@ const char* get_version_main(version_t* ptr) {
 	if (ptr) {
 		return ptr->obj->get_version_main();
 	}
+	return nullptr;
 }
 
 template <typename SGType, typename... Args>
 sgobject_result create_helper(Args&&... args) {
-	try {
+	return exception_firewall([&]() -> sgobject_result {
 		auto obj = create<SGType>(std::forward<Args>(args)...);
 		auto* ptr = new sgobject_t(obj);
 		return {RETURN_CODE::SUCCESS, ptr};
+	});
+}
+
+/** Creates an object from its class name, reporting names that are not in
+ * Shogun's class registry as UNKNOWN_CLASS and classes with another base
+ * class as TYPE_MISMATCH. Only if creating the object failed, the name is
+ * looked up in the registry and the object is constructed once more with
+ * its own class to tell the two apart from failing constructors.
+ */
+template <typename SGType>
+sgobject_result create_from_name_helper(const char* name) {
+	auto result = create_helper<SGType>(name);
+	if (result.return_code == RETURN_CODE::ERROR) {
+		try {
+			const auto names = available_objects();
+			if (std::find(names.begin(), names.end(), name) == names.end())
+				result.error_code = ERROR_CODE::UNKNOWN_CLASS;
+			else if (auto obj = create(name, PT_NOT_GENERIC); obj && !std::dynamic_pointer_cast<SGType>(obj))
+				result.error_code = ERROR_CODE::TYPE_MISMATCH;
+		}
+		catch (...) {}
 	}
-	catch (const std::exception& e) {
-		sgobject_result result;
-		result.return_code = RETURN_CODE::ERROR;
-		result.result.error = e.what();
-		return result;
+	return result;
+}
+
+/** Names in Shogun's class registry. The registry does not record base
+ * classes and finding them would mean constructing every class, so the
+ * classes of all base types are listed.
+ */
+string_list_t* available_classes() {
+	// an empty list is returned if the registry cannot be queried and
+	// nullptr if the list itself cannot be allocated
+	try {
+		auto list = std::make_unique<string_list_t>();
+		try {
+			const auto names = available_objects();
+			list->strings.assign(names.begin(), names.end());
+		}
+		catch (...) {
+			list->strings.clear();
+		}
+		return list.release();
+	}
+	catch (...) {
+		return nullptr;
 	}
 }
 
+void destroy_string_list(string_list_t* ptr) {
+	delete ptr;
+}
+
+uint32_t get_string_list_size(const string_list_t* ptr) {
+	return ptr->strings.size();
+}
+
+const char* get_string_list_element(const string_list_t* ptr, uint32_t idx) {
+	return ptr->strings[idx].c_str();
+}
+
 sgobject_result create_machine(const char* name) {
-	return create_helper<Machine>(name);
+	return create_from_name_helper<Machine>(name);
 }
 
 Result train_machine(sgobject_t* machine, sgobject_t* features) {
 	if (!std::holds_alternative<std::shared_ptr<Machine>>(machine->ptr))
-		return {RETURN_CODE::ERROR, "Expected training to be done with Machine type"};
+		return error_result<Result>(ERROR_CODE::INVALID_INPUT, "Expected training to be done with Machine type");
 	if (!std::holds_alternative<std::shared_ptr<Features>>(features->ptr))
-		return {RETURN_CODE::ERROR, "Expected training to be done on Features type"};
-	try {
+		return error_result<Result>(ERROR_CODE::INVALID_INPUT, "Expected training to be done on Features type");
+	return exception_firewall<Result>([&]() -> Result {
+		auto lock = machine->write_lock({features});
 		std::get<std::shared_ptr<Machine>>(machine->ptr)->train(
 			std::get<std::shared_ptr<Features>>(features->ptr)
 		);
 		return {RETURN_CODE::SUCCESS, nullptr};
-	}
-	catch (std::exception& e) {
-		return {RETURN_CODE::ERROR, e.what()};
-	}
+	});
 }
 
 template <typename T, typename ResultType=sgobject_result>
 std::optional<ResultType> check_type(const sgobject_t* obj, const char* error_msg) {
-	if (!std::holds_alternative<std::shared_ptr<T>>(obj->ptr)) {
-		ResultType result;
-		result.return_code = RETURN_CODE::ERROR;
-		result.result.error = error_msg;
-		return result;
-	}
+	if (!std::holds_alternative<std::shared_ptr<T>>(obj->ptr))
+		return error_result<ResultType>(ERROR_CODE::INVALID_INPUT, error_msg);
 	return {};
 }
 
@@ -279,17 +805,14 @@ sgobject_result apply_machine(sgobject_t* machine, sgobject_t* features) {
 		return *result;
 	if (auto result = check_type<Features>(features, "Expected inference to be done on Features type"))
 		return *result;
-	try {
+	return exception_firewall([&]() -> sgobject_result {
+		// machines update internal state while predicting, such as the rhs
+		// of a kernel, so predictions with the same machine are exclusive
+		auto lock = machine->write_lock({}, {features});
 		auto result = std::get<std::shared_ptr<Machine>>(machine->ptr)->apply(std::get<std::shared_ptr<Features>>(features->ptr));
 		auto* ptr = new sgobject_t(result);
 		return {RETURN_CODE::SUCCESS, ptr};
-	}
-	catch (const std::exception& e) {
-		sgobject_result result;
-		result.return_code = RETURN_CODE::ERROR;
-		result.result.error = e.what();
-		return result;
-	} 
+	});
 }
 
 sgobject_result apply_multiclass_machine(sgobject_t* machine, sgobject_t* features) {
@@ -297,25 +820,20 @@ sgobject_result apply_multiclass_machine(sgobject_t* machine, sgobject_t* featur
 		return *result;
 	if (auto result = check_type<Features>(features, "Expected inference to be done on Features type"))
 		return *result;
-	try {
+	return exception_firewall([&]() -> sgobject_result {
+		auto lock = machine->write_lock({}, {features});
 		auto result = std::get<std::shared_ptr<Machine>>(machine->ptr)->apply_multiclass(std::get<std::shared_ptr<Features>>(features->ptr));
 		auto* ptr = new sgobject_t(std::static_pointer_cast<Labels>(result));
 		return {RETURN_CODE::SUCCESS, ptr};
-	}
-	catch (const std::exception& e) {
-		sgobject_result result;
-		result.return_code = RETURN_CODE::ERROR;
-		result.result.error = e.what();
-		return result;
-	} 
+	});
 }
 
 sgobject_result create_kernel(const char* name) {
-	return create_helper<Kernel>(name);
+	return create_from_name_helper<Kernel>(name);
 }
 
 sgobject_result create_distance(const char* name) {
-	return create_helper<Distance>(name);
+	return create_from_name_helper<Distance>(name);
 }
 
 void destroy_sgobject(sgobject* ptr) {
@@ -325,30 +843,52 @@ void destroy_sgobject(sgobject* ptr) {
 }
 
 const char* to_string(const sgobject_t* ptr) {
-	if (ptr) {
-		return ptr->to_string();
+	if (!ptr)
+		return copy_c_string("Null SGObject");
+	return ptr->to_string();
+}
+
+const char* sgobject_class_name(const sgobject_t* ptr) {
+	try {
+		auto lock = ptr->read_lock();
+		return copy_c_string(ptr->get_name());
+	}
+	catch (...) {
+		return copy_c_string("SGObject");
 	}
 }
 
-cvisitor_t* sgobject_get(const sgobject_t* ptr, const char* name) {
-	const auto& param = ptr->get_parameter(name);
-	auto* visitor = new C_Visitor{};
-	param.visit_with(visitor);
-	return visitor;
+cvisitor_result sgobject_get(const sgobject_t* ptr, const char* name) {
+	return exception_firewall<cvisitor_result>([&]() -> cvisitor_result {
+		auto lock = ptr->read_lock();
+		const auto& param = ptr->get_parameter(name);
+		auto visitor = std::make_unique<C_Visitor>();
+		// enums are handed out as the name of their option
+		if (ptr->enum_options(name)) {
+			visitor->m_type = {ENUM, "enum"};
+			visitor->m_value = (void*)copy_c_string(ptr->get_enum(name));
+			visitor->m_deleter = [](void* value) { free_string(static_cast<const char*>(value)); };
+			return {RETURN_CODE::SUCCESS, visitor.release()};
+		}
+		param.visit_with(visitor.get());
+		return {RETURN_CODE::SUCCESS, visitor.release()};
+	});
 }
 
 Result sgobject_put(sgobject_t* ptr, const char* name, const void* value, TYPE type) {
-	const auto& param = ptr->get_parameter(name);
-	auto visitor = Put_Visitor{value, type};
-	if (type == SGOBJECT)
-		visitor.m_value = std::visit([](auto&& obj){return (void*)&obj;}, static_cast<const sgobject_t*>(value)->ptr);
-	try {
+	return exception_firewall<Result>([&]() -> Result {
+		auto visitor = Put_Visitor{value, type};
+		if (type == SGOBJECT)
+			visitor.m_value = std::visit([](auto&& obj){return (void*)&obj;}, static_cast<const sgobject_t*>(value)->ptr);
+		auto lock = type == SGOBJECT ? ptr->write_lock({static_cast<const sgobject_t*>(value)}) : ptr->write_lock();
+		const auto& param = ptr->get_parameter(name);
+		if ((type == STRING || type == ENUM) && ptr->enum_options(name)) {
+			ptr->put_enum(name, static_cast<const char*>(value));
+			return {RETURN_CODE::SUCCESS, nullptr};
+		}
 		param.visit_with(&visitor);
 		return {RETURN_CODE::SUCCESS, nullptr};
-	}
-	catch(const std::exception& e) {
-		return {RETURN_CODE::ERROR, e.what()};
-	}
+	});
 }
 
 template <typename T>
@@ -358,61 +898,252 @@ SGMatrix<T> create_matrix_with_copy(const T* data, uint32_t rows, uint32_t cols)
 	return mat;
 }
 
-Result sgobject_put_array(sgobject_t* ptr, const char* name, const void* data, uint32_t rows, uint32_t cols, TYPE type) {
-	try {
+template <typename T>
+SGVector<T> create_vector_with_copy(const T* data, uint32_t len) {
+	auto vec = SGVector<T>(len);
+	sg_memcpy(vec.vector, data, len*sizeof(T));
+	return vec;
+}
+
+template <typename T>
+void put_array_helper(const Any& param, const void* data, uint32_t rows, uint32_t cols, TYPE type, CONTAINER_TYPE container) {
+	const auto* casted_data = static_cast<const T*>(data);
+	switch (container)
+	{
+	case CONTAINER_TYPE::CT_VECTOR: {
+		// vectors are passed as a single column
+		if (cols != 1)
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Expected a vector to have a single column");
+		const auto vec = create_vector_with_copy(casted_data, rows);
+		Put_Visitor visitor{(const void*) &vec, type, CONTAINER_TYPE::CT_VECTOR};
+		param.visit_with(&visitor);
+		break;
+	}
+	case CONTAINER_TYPE::CT_MATRIX: {
+		const auto mat = create_matrix_with_copy(casted_data, rows, cols);
+		Put_Visitor visitor{(const void*) &mat, type, CONTAINER_TYPE::CT_MATRIX};
+		param.visit_with(&visitor);
+		break;
+	}
+	default:
+		throw ShimException(ERROR_CODE::INVALID_INPUT, "Expected an SGVector or SGMatrix container");
+	}
+}
+
+Result sgobject_put_array(sgobject_t* ptr, const char* name, const void* data, uint32_t rows, uint32_t cols, TYPE type, CONTAINER_TYPE container) {
+	return exception_firewall<Result>([&]() -> Result {
+		auto lock = ptr->write_lock();
 		const auto& param = ptr->get_parameter(name);
-		// it's a vector
-		if (rows == 0) {
-			// create_matrix_with_copy(const T* data, uint32_t rows, uint32_t cols)
-			error("SGVector not implemented yet.");
-		}
-		else {
-			switch (type)
-			{
-			case TYPE::FLOAT32: {
-				const auto* casted_data = static_cast<const float32_t*>(data);
-				const auto mat = create_matrix_with_copy(casted_data, rows, cols);
-				Put_Visitor visitor{(const void*) &mat};
-				param.visit_with(&visitor);
-			} break;
-			case TYPE::FLOAT64: {
-				auto* casted_data = static_cast<const float64_t*>(data);
-				const auto mat = create_matrix_with_copy(casted_data, rows, cols);
-				Put_Visitor visitor{(const void*) &mat};
-				param.visit_with(&visitor);
-			} break;
-			case TYPE::INT32: {
-				auto* casted_data = static_cast<const int32_t*>(data);
-				const auto mat = create_matrix_with_copy(casted_data, rows, cols);
-				Put_Visitor visitor{(const void*) &mat};
-				param.visit_with(&visitor);
-			} break;
-			case TYPE::INT64: {
-				auto* casted_data = static_cast<const int64_t*>(data);
-				const auto mat = create_matrix_with_copy(casted_data, rows, cols);
-				Put_Visitor visitor{(const void*) &mat};
-				param.visit_with(&visitor);
-			} break;
-			default: {
-				return {RETURN_CODE::ERROR, "Cannot handle scalar type for SGMatrix"};	
-			};
-			}
+		switch (type)
+		{
+		case TYPE::FLOAT32:
+			put_array_helper<float32_t>(param, data, rows, cols, type, container);
+			break;
+		case TYPE::FLOAT64:
+			put_array_helper<float64_t>(param, data, rows, cols, type, container);
+			break;
+		case TYPE::INT32:
+			put_array_helper<int32_t>(param, data, rows, cols, type, container);
+			break;
+		case TYPE::INT64:
+			put_array_helper<int64_t>(param, data, rows, cols, type, container);
+			break;
+		default: {
+			return error_result<Result>(ERROR_CODE::INVALID_INPUT, "Cannot handle scalar type for SGVector or SGMatrix");
+		};
 		}
 		return {RETURN_CODE::SUCCESS, nullptr};
-	}
-	catch(const std::exception& e) {
-		return {RETURN_CODE::ERROR, e.what()};
-	}
+	});
 }
 
 SG_TYPE sgobject_derived_type(const sgobject_t* ptr) {
 	return ptr->derived_type();
 }
 
+sgobject_result sgobject_clone(const sgobject_t* ptr) {
+	return exception_firewall([&]() -> sgobject_result {
+		auto lock = ptr->read_lock();
+		return std::visit([](auto&& obj) -> sgobject_result {
+			using SGType = typename std::decay_t<decltype(obj)>::element_type;
+			auto clone = std::dynamic_pointer_cast<SGType>(obj->clone());
+			if (!clone)
+				return error_result(ERROR_CODE::NATIVE_EXCEPTION, ("Failed to clone " + obj->get_name()).c_str());
+			return {RETURN_CODE::SUCCESS, new sgobject_t(clone)};
+		}, ptr->ptr);
+	});
+}
+
+bool_result sgobject_equals(const sgobject_t* lhs, const sgobject_t* rhs) {
+	return exception_firewall<bool_result>([&]() -> bool_result {
+		auto lock = lhs->read_lock({rhs});
+		// Shogun compares the class names and all registered parameters
+		auto equal = std::visit([](auto&& lhs_obj, auto&& rhs_obj) {
+			return lhs_obj->equals(rhs_obj.get());
+		}, lhs->ptr, rhs->ptr);
+		return {RETURN_CODE::SUCCESS, equal};
+	});
+}
+
+sgobject_result sgobject_share(const sgobject_t* ptr) {
+	return exception_firewall([&]() -> sgobject_result {
+		return {RETURN_CODE::SUCCESS, new sgobject_t(*ptr)};
+	});
+}
+
+/** Wraps an object of unknown type in the handle of its base type */
+sgobject_t* make_sgobject(const std::shared_ptr<SGObject>& obj) {
+	if (auto machine = std::dynamic_pointer_cast<Machine>(obj))
+		return new sgobject_t(machine);
+	if (auto kernel = std::dynamic_pointer_cast<Kernel>(obj))
+		return new sgobject_t(kernel);
+	if (auto distance = std::dynamic_pointer_cast<Distance>(obj))
+		return new sgobject_t(distance);
+	if (auto features = std::dynamic_pointer_cast<Features>(obj))
+		return new sgobject_t(features);
+	if (auto file = std::dynamic_pointer_cast<File>(obj))
+		return new sgobject_t(file);
+	if (auto rule = std::dynamic_pointer_cast<CombinationRule>(obj))
+		return new sgobject_t(rule);
+	if (auto labels = std::dynamic_pointer_cast<Labels>(obj))
+		return new sgobject_t(labels);
+	if (auto evaluation = std::dynamic_pointer_cast<Evaluation>(obj))
+		return new sgobject_t(evaluation);
+	throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Cannot handle objects of class " + obj->get_name());
+}
+
+std::shared_ptr<io::Serializer> make_serializer(SERIALIZATION_FORMAT format) {
+	switch (format)
+	{
+	case SERIALIZATION_FORMAT::JSON:
+		return std::make_shared<io::JsonSerializer>();
+#ifdef SHIM_HAS_BINARY_SERIALIZATION
+	case SERIALIZATION_FORMAT::BINARY:
+		return std::make_shared<io::BitserySerializer>();
+#endif
+	default:
+		throw ShimException(ERROR_CODE::INVALID_INPUT, "Serialization format is not supported by this Shogun build");
+	}
+}
+
+std::shared_ptr<io::Deserializer> make_deserializer(SERIALIZATION_FORMAT format) {
+	switch (format)
+	{
+	case SERIALIZATION_FORMAT::JSON:
+		return std::make_shared<io::JsonDeserializer>();
+#ifdef SHIM_HAS_BINARY_SERIALIZATION
+	case SERIALIZATION_FORMAT::BINARY:
+		return std::make_shared<io::BitseryDeserializer>();
+#endif
+	default:
+		throw ShimException(ERROR_CODE::INVALID_INPUT, "Serialization format is not supported by this Shogun build");
+	}
+}
+
+std::string serialize(const sgobject_t* ptr, SERIALIZATION_FORMAT format) {
+	auto serializer = make_serializer(format);
+	auto stream = std::make_shared<io::ByteArrayOutputStream>();
+	serializer->attach(stream);
+	{
+		auto lock = ptr->read_lock();
+		std::visit([&](auto&& obj) { serializer->write(obj); }, ptr->ptr);
+	}
+	const auto content = stream->content();
+	return std::string(content.vector, content.vlen);
+}
+
+sgobject_t* deserialize(const std::string& data, SERIALIZATION_FORMAT format) {
+	auto deserializer = make_deserializer(format);
+	auto stream = std::make_shared<io::ByteArrayInputStream>(data.data(), data.size());
+	deserializer->attach(stream);
+	return make_sgobject(deserializer->read_object());
+}
+
+string_result sgobject_to_json(const sgobject_t* ptr) {
+	return exception_firewall<string_result>([&]() -> string_result {
+		return {RETURN_CODE::SUCCESS, copy_c_string(serialize(ptr, SERIALIZATION_FORMAT::JSON))};
+	});
+}
+
+sgobject_result sgobject_from_json(const char* json) {
+	return exception_firewall([&]() -> sgobject_result {
+		return {RETURN_CODE::SUCCESS, deserialize(json, SERIALIZATION_FORMAT::JSON)};
+	});
+}
+
+Result sgobject_save(const sgobject_t* ptr, const char* path, SERIALIZATION_FORMAT format) {
+	return exception_firewall<Result>([&]() -> Result {
+		const auto data = serialize(ptr, format);
+		std::ofstream file(path, std::ios::binary);
+		file.write(data.data(), data.size());
+		if (!file)
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Failed to write " + std::string(path));
+		return {RETURN_CODE::SUCCESS, nullptr};
+	});
+}
+
+sgobject_result sgobject_load(const char* path, SERIALIZATION_FORMAT format) {
+	return exception_firewall([&]() -> sgobject_result {
+		std::ifstream file(path, std::ios::binary);
+		if (!file)
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Failed to open " + std::string(path));
+		const std::string data{std::istreambuf_iterator<char>(file), std::istreambuf_iterator<char>()};
+		return {RETURN_CODE::SUCCESS, deserialize(data, format)};
+	});
+}
+
+parameter_list_result sgobject_parameters(const sgobject_t* ptr) {
+	return exception_firewall<parameter_list_result>([&]() -> parameter_list_result {
+		auto lock = ptr->read_lock();
+		auto list = std::make_unique<parameter_list_t>();
+		for (const auto& [name, param]: ptr->get_params()) {
+			const auto& properties = param->get_properties();
+			uint32_t flags = 0;
+			if (properties.has_property(ParameterProperties::HYPER))
+				flags |= PARAMETER_PROPERTY::HYPERPARAMETER;
+			if (properties.has_property(ParameterProperties::GRADIENT))
+				flags |= PARAMETER_PROPERTY::GRADIENT_PARAMETER;
+			if (properties.has_property(ParameterProperties::MODEL))
+				flags |= PARAMETER_PROPERTY::MODEL_PARAMETER;
+			if (properties.has_property(ParameterProperties::READONLY))
+				flags |= PARAMETER_PROPERTY::READONLY_PARAMETER;
+			list->entries.push_back({name, param->get_value().type(), properties.get_description(), flags});
+		}
+		return {RETURN_CODE::SUCCESS, list.release()};
+	});
+}
+
+void destroy_parameter_list(parameter_list_t* ptr) {
+	delete ptr;
+}
+
+uint32_t get_parameter_list_size(const parameter_list_t* ptr) {
+	return ptr->entries.size();
+}
+
+const char* get_parameter_list_name(const parameter_list_t* ptr, uint32_t idx) {
+	return ptr->entries[idx].name.c_str();
+}
+
+const char* get_parameter_list_typename(const parameter_list_t* ptr, uint32_t idx) {
+	return ptr->entries[idx].type_name.c_str();
+}
+
+const char* get_parameter_list_description(const parameter_list_t* ptr, uint32_t idx) {
+	return ptr->entries[idx].description.c_str();
+}
+
+uint32_t get_parameter_list_properties(const parameter_list_t* ptr, uint32_t idx) {
+	return ptr->entries[idx].properties;
+}
+
 TYPE get_cvisitor_type(const cvisitor_t* ptr) {
 	return ptr->m_type.first;
 }
 
+CONTAINER_TYPE get_cvisitor_container(const cvisitor_t* ptr) {
+	return ptr->m_container;
+}
+
 const char* get_cvisitor_typename(const cvisitor_t* ptr) {
 	return ptr->m_type.second.data();
 }
@@ -421,11 +1152,45 @@ void* get_cvisitor_pointer(const cvisitor_t* ptr) {
 	return ptr->m_value;
 }
 
+void* release_cvisitor_pointer(cvisitor_t* ptr) {
+	auto* value = ptr->m_value;
+	ptr->m_value = nullptr;
+	return value;
+}
+
+void destroy_cvisitor(cvisitor_t* ptr) {
+	if (ptr) {
+		if (ptr->m_value && ptr->m_deleter)
+			ptr->m_deleter(ptr->m_value);
+		delete ptr;
+	}
+}
+
+void free_string(const char* ptr) {
+	if (ptr) {
+		free((void*)ptr);
+		--live_allocations;
+	}
+}
+
+int64_t get_live_allocations() {
+	return live_allocations;
+}
+
+uint32_t get_cvisitor_rows(const cvisitor_t* ptr) {
+	return ptr->m_rows;
+}
+
+uint32_t get_cvisitor_cols(const cvisitor_t* ptr) {
+	return ptr->m_cols;
+}
+
 sgobject_result create_features(const char* name) {
-	return create_helper<Features>(name);
+	return create_from_name_helper<Features>(name);
 }
 
 sgobject_result create_features_from_data(const void* data, uint32_t rows, uint32_t cols, TYPE type) {
+	return exception_firewall([&]() -> sgobject_result {
 	switch (type)
 	{
 	case TYPE::FLOAT32: {
@@ -449,52 +1214,423 @@ sgobject_result create_features_from_data(const void* data, uint32_t rows, uint3
 		return create_helper<Features>(mat);
 	} break;
 	default: {
-		sgobject_result result;
-		result.return_code = RETURN_CODE::ERROR;
-		result.result.error = "Cannot create a Features object from provided data";
-		return result;	
+		return error_result(ERROR_CODE::INVALID_INPUT, "Cannot create a Features object from provided data");
 	};
 	}
+	});
+}
+
+/** Creates DenseFeatures backed by a non-owning SGMatrix, the caller
+ * has to keep the column-major data alive while the features are used.
+ */
+template <typename T>
+sgobject_result create_borrowed_features_helper(void* data, uint32_t rows, uint32_t cols) {
+	auto* casted_data = static_cast<T*>(data);
+	auto mat = SGMatrix<T>(casted_data, rows, cols, false);
+	return create_helper<Features>(mat);
+}
+
+sgobject_result create_features_from_borrowed_data(void* data, uint32_t rows, uint32_t cols, TYPE type) {
+	return exception_firewall([&]() -> sgobject_result {
+	switch (type)
+	{
+	case TYPE::FLOAT32:
+		return create_borrowed_features_helper<float32_t>(data, rows, cols);
+	case TYPE::FLOAT64:
+		return create_borrowed_features_helper<float64_t>(data, rows, cols);
+	case TYPE::INT32:
+		return create_borrowed_features_helper<int32_t>(data, rows, cols);
+	case TYPE::INT64:
+		return create_borrowed_features_helper<int64_t>(data, rows, cols);
+	default:
+		return error_result(ERROR_CODE::INVALID_INPUT, "Cannot create a Features object from provided data");
+	}
+	});
+}
+
+template <typename T>
+sgobject_result create_sparse_features_helper(const uint64_t* indptr, const uint64_t* indices, const void* data, uint32_t num_features, uint32_t num_vectors) {
+	const auto* casted_data = static_cast<const T*>(data);
+	SGSparseMatrix<T> matrix(num_features, num_vectors);
+	for (uint32_t i = 0; i < num_vectors; ++i) {
+		const auto begin = indptr[i];
+		SGSparseVector<T> vector(indptr[i + 1] - begin);
+		for (index_t j = 0; j < vector.num_feat_entries; ++j) {
+			if (indices[begin + j] >= num_features)
+				throw ShimException(ERROR_CODE::INVALID_INPUT, "Sparse feature index out of bounds");
+			vector.features[j].feat_index = indices[begin + j];
+			vector.features[j].entry = casted_data[begin + j];
+		}
+		matrix.sparse_matrix[i] = vector;
+	}
+	auto features = std::make_shared<SparseFeatures<T>>(matrix);
+	return {RETURN_CODE::SUCCESS, new sgobject_t(std::static_pointer_cast<Features>(features))};
+}
+
+sgobject_result create_sparse_features(const uint64_t* indptr, const uint64_t* indices, const void* data, uint32_t num_features, uint32_t num_vectors, TYPE type) {
+	return exception_firewall([&]() -> sgobject_result {
+		switch (type)
+		{
+		case TYPE::FLOAT32:
+			return create_sparse_features_helper<float32_t>(indptr, indices, data, num_features, num_vectors);
+		case TYPE::FLOAT64:
+			return create_sparse_features_helper<float64_t>(indptr, indices, data, num_features, num_vectors);
+		default:
+			return error_result(ERROR_CODE::INVALID_INPUT, "Sparse features only support float32 and float64 data");
+		}
+	});
+}
+
+template <typename T>
+sparse_matrix_t* sparse_matrix_helper(const std::shared_ptr<SparseFeatures<T>>& features) {
+	const auto matrix = features->get_sparse_feature_matrix();
+	auto result = std::make_unique<sparse_matrix_t>();
+	result->type = get_type<T>::type.first;
+	result->num_features = matrix.num_features;
+	result->num_vectors = matrix.num_vectors;
+	result->indptr.push_back(0);
+	std::vector<T> data;
+	for (index_t i = 0; i < matrix.num_vectors; ++i) {
+		const auto& vector = matrix.sparse_matrix[i];
+		std::vector<SGSparseVectorEntry<T>> entries(vector.features, vector.features + vector.num_feat_entries);
+		std::sort(entries.begin(), entries.end(), [](const auto& lhs, const auto& rhs) {
+			return lhs.feat_index < rhs.feat_index;
+		});
+		for (const auto& entry: entries) {
+			result->indices.push_back(entry.feat_index);
+			data.push_back(entry.entry);
+		}
+		result->indptr.push_back(result->indices.size());
+	}
+	const auto* bytes = reinterpret_cast<const char*>(data.data());
+	result->data.assign(bytes, bytes + data.size() * sizeof(T));
+	return result.release();
+}
+
+sparse_matrix_result get_sparse_feature_matrix(const sgobject_t* ptr) {
+	if (auto result = check_type<Features, sparse_matrix_result>(ptr, "Expected self to be of type Features"))
+		return *result;
+	return exception_firewall<sparse_matrix_result>([&]() -> sparse_matrix_result {
+		auto lock = ptr->read_lock();
+		const auto& features = std::get<std::shared_ptr<Features>>(ptr->ptr);
+		if (auto sparse = std::dynamic_pointer_cast<SparseFeatures<float32_t>>(features))
+			return {RETURN_CODE::SUCCESS, sparse_matrix_helper(sparse)};
+		if (auto sparse = std::dynamic_pointer_cast<SparseFeatures<float64_t>>(features))
+			return {RETURN_CODE::SUCCESS, sparse_matrix_helper(sparse)};
+		return error_result<sparse_matrix_result>(ERROR_CODE::TYPE_MISMATCH, "Expected float32 or float64 sparse features");
+	});
+}
+
+void destroy_sparse_matrix(sparse_matrix_t* ptr) {
+	if (ptr) {
+		delete ptr;
+	}
+}
+
+TYPE get_sparse_matrix_type(const sparse_matrix_t* ptr) {
+	return ptr->type;
+}
+
+uint32_t get_sparse_matrix_num_features(const sparse_matrix_t* ptr) {
+	return ptr->num_features;
+}
+
+uint32_t get_sparse_matrix_num_vectors(const sparse_matrix_t* ptr) {
+	return ptr->num_vectors;
+}
+
+uint64_t get_sparse_matrix_nnz(const sparse_matrix_t* ptr) {
+	return ptr->indices.size();
+}
+
+const uint64_t* get_sparse_matrix_indptr(const sparse_matrix_t* ptr) {
+	return ptr->indptr.data();
+}
+
+const uint64_t* get_sparse_matrix_indices(const sparse_matrix_t* ptr) {
+	return ptr->indices.data();
+}
+
+const void* get_sparse_matrix_data(const sparse_matrix_t* ptr) {
+	return ptr->data.data();
+}
+
+EAlphabet to_shogun_alphabet(ALPHABET alphabet) {
+	switch (alphabet)
+	{
+	case ALPHABET::AL_DNA:
+		return EAlphabet::DNA;
+	case ALPHABET::AL_RAWDNA:
+		return EAlphabet::RAWDNA;
+	case ALPHABET::AL_RNA:
+		return EAlphabet::RNA;
+	case ALPHABET::AL_PROTEIN:
+		return EAlphabet::PROTEIN;
+	case ALPHABET::AL_BINARY:
+		return EAlphabet::BINARY;
+	case ALPHABET::AL_ALPHANUM:
+		return EAlphabet::ALPHANUM;
+	case ALPHABET::AL_CUBE:
+		return EAlphabet::CUBE;
+	case ALPHABET::AL_RAWBYTE:
+		return EAlphabet::RAWBYTE;
+	case ALPHABET::AL_IUPAC_NUCLEIC_ACID:
+		return EAlphabet::IUPAC_NUCLEIC_ACID;
+	case ALPHABET::AL_IUPAC_AMINO_ACID:
+		return EAlphabet::IUPAC_AMINO_ACID;
+	case ALPHABET::AL_DIGIT:
+		return EAlphabet::DIGIT;
+	case ALPHABET::AL_DIGIT2:
+		return EAlphabet::DIGIT2;
+	case ALPHABET::AL_RAWDIGIT:
+		return EAlphabet::RAWDIGIT;
+	case ALPHABET::AL_RAWDIGIT2:
+		return EAlphabet::RAWDIGIT2;
+	case ALPHABET::AL_SNP:
+		return EAlphabet::SNP;
+	case ALPHABET::AL_RAWSNP:
+		return EAlphabet::RAWSNP;
+	}
+	throw ShimException(ERROR_CODE::INVALID_INPUT, "Unknown alphabet");
+}
+
+sgobject_result create_string_features(const char* data, const uint64_t* offsets, uint32_t num_strings, ALPHABET alphabet) {
+	return exception_firewall([&]() -> sgobject_result {
+		std::vector<SGVector<char>> strings;
+		strings.reserve(num_strings);
+		for (uint32_t i = 0; i < num_strings; ++i) {
+			SGVector<char> string(offsets[i + 1] - offsets[i]);
+			std::copy(data + offsets[i], data + offsets[i + 1], string.vector);
+			strings.push_back(string);
+		}
+		// Shogun throws if a string has symbols outside of the alphabet
+		auto features = std::make_shared<StringFeatures<char>>(strings, to_shogun_alphabet(alphabet));
+		return {RETURN_CODE::SUCCESS, new sgobject_t(std::static_pointer_cast<Features>(features))};
+	});
+}
+
+uint64_result get_features_num_vectors(const sgobject_t* ptr) {
+	if (auto result = check_type<Features, uint64_result>(ptr, "Expected self to be of type Features"))
+		return *result;
+	return exception_firewall<uint64_result>([&]() -> uint64_result {
+		auto lock = ptr->read_lock();
+		const auto& features = std::get<std::shared_ptr<Features>>(ptr->ptr);
+		return {RETURN_CODE::SUCCESS, static_cast<uint64_t>(features->get_num_vectors())};
+	});
+}
+
+uint64_result get_features_num_features(const sgobject_t* ptr) {
+	if (auto result = check_type<Features, uint64_result>(ptr, "Expected self to be of type Features"))
+		return *result;
+	return exception_firewall<uint64_result>([&]() -> uint64_result {
+		auto lock = ptr->read_lock();
+		const auto& features = std::get<std::shared_ptr<Features>>(ptr->ptr);
+		// covers dense and sparse features
+		if (auto dot_features = std::dynamic_pointer_cast<DotFeatures>(features))
+			return {RETURN_CODE::SUCCESS, static_cast<uint64_t>(dot_features->get_dim_feature_space())};
+		// strings can have different lengths, the longest one is used
+		if (auto string_features = std::dynamic_pointer_cast<StringFeatures<char>>(features))
+			return {RETURN_CODE::SUCCESS, static_cast<uint64_t>(string_features->get_max_vector_length())};
+		auto msg = std::string("Number of features is not defined for ") + features->get_name();
+		return error_result<uint64_result>(ERROR_CODE::TYPE_MISMATCH, msg.c_str());
+	});
+}
+
+FEATURE_CLASS get_features_class(const sgobject_t* ptr) {
+	try {
+		if (!std::holds_alternative<std::shared_ptr<Features>>(ptr->ptr))
+			return FEATURE_CLASS::FC_OTHER;
+		auto lock = ptr->read_lock();
+		switch (std::get<std::shared_ptr<Features>>(ptr->ptr)->get_feature_class())
+		{
+		case EFeatureClass::C_DENSE:
+			return FEATURE_CLASS::FC_DENSE;
+		case EFeatureClass::C_SPARSE:
+			return FEATURE_CLASS::FC_SPARSE;
+		case EFeatureClass::C_STRING:
+			return FEATURE_CLASS::FC_STRING;
+		case EFeatureClass::C_COMBINED:
+		case EFeatureClass::C_COMBINED_DOT:
+			return FEATURE_CLASS::FC_COMBINED;
+		default:
+			return FEATURE_CLASS::FC_OTHER;
+		}
+	}
+	catch (...) {
+		return FEATURE_CLASS::FC_OTHER;
+	}
+}
+
+type_result get_features_type(const sgobject_t* ptr) {
+	if (auto result = check_type<Features, type_result>(ptr, "Expected self to be of type Features"))
+		return *result;
+	return exception_firewall<type_result>([&]() -> type_result {
+		auto lock = ptr->read_lock();
+		const auto& features = std::get<std::shared_ptr<Features>>(ptr->ptr);
+		switch (features->get_feature_type())
+		{
+		case EFeatureType::F_BOOL:
+			return {RETURN_CODE::SUCCESS, TYPE::BOOL};
+		case EFeatureType::F_CHAR:
+			return {RETURN_CODE::SUCCESS, TYPE::CHAR};
+		case EFeatureType::F_INT:
+			return {RETURN_CODE::SUCCESS, TYPE::INT32};
+		case EFeatureType::F_UINT:
+			return {RETURN_CODE::SUCCESS, TYPE::UINT32};
+		case EFeatureType::F_LONG:
+			return {RETURN_CODE::SUCCESS, TYPE::INT64};
+		case EFeatureType::F_ULONG:
+			return {RETURN_CODE::SUCCESS, TYPE::UINT64};
+		case EFeatureType::F_SHORTREAL:
+			return {RETURN_CODE::SUCCESS, TYPE::FLOAT32};
+		case EFeatureType::F_DREAL:
+			return {RETURN_CODE::SUCCESS, TYPE::FLOAT64};
+		default:
+			auto msg = std::string("Feature type of ") + features->get_name() + " has no equivalent TYPE";
+			return error_result<type_result>(ERROR_CODE::TYPE_MISMATCH, msg.c_str());
+		}
+	});
+}
+
+template <typename T>
+void add_subset_helper(const std::shared_ptr<T>& obj, index_t num_samples, const uint64_t* indices, uint64_t num_indices) {
+	SGVector<index_t> subset(num_indices);
+	for (uint64_t i = 0; i < num_indices; ++i) {
+		// indices refer to the samples of the current subset, if any
+		if (indices[i] >= static_cast<uint64_t>(num_samples))
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Subset index out of bounds");
+		subset[i] = indices[i];
+	}
+	obj->add_subset(subset);
+}
+
+Result add_subset(sgobject_t* ptr, const uint64_t* indices, uint64_t num_indices) {
+	return exception_firewall<Result>([&]() -> Result {
+		auto lock = ptr->write_lock();
+		if (const auto* features = std::get_if<std::shared_ptr<Features>>(&ptr->ptr))
+			add_subset_helper(*features, (*features)->get_num_vectors(), indices, num_indices);
+		else if (const auto* labels = std::get_if<std::shared_ptr<Labels>>(&ptr->ptr))
+			add_subset_helper(*labels, (*labels)->get_num_labels(), indices, num_indices);
+		else
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Expected self to be of type Features or Labels");
+		return {RETURN_CODE::SUCCESS, nullptr};
+	});
+}
+
+Result remove_subset(sgobject_t* ptr) {
+	return exception_firewall<Result>([&]() -> Result {
+		auto lock = ptr->write_lock();
+		if (const auto* features = std::get_if<std::shared_ptr<Features>>(&ptr->ptr))
+			(*features)->remove_subset();
+		else if (const auto* labels = std::get_if<std::shared_ptr<Labels>>(&ptr->ptr))
+			(*labels)->remove_subset();
+		else
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Expected self to be of type Features or Labels");
+		return {RETURN_CODE::SUCCESS, nullptr};
+	});
+}
+
+Result combined_features_append(sgobject_t* combined, const sgobject_t* features) {
+	if (auto result = check_type<Features, Result>(combined, "Expected self to be of type Features"))
+		return *result;
+	if (auto result = check_type<Features, Result>(features, "Expected to append Features"))
+		return *result;
+	return exception_firewall<Result>([&]() -> Result {
+		auto lock = combined->write_lock({features});
+		auto combined_features = std::dynamic_pointer_cast<CombinedFeatures>(std::get<std::shared_ptr<Features>>(combined->ptr));
+		if (!combined_features)
+			throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Expected self to be CombinedFeatures");
+		// Shogun rejects features with another number of vectors
+		if (!combined_features->append_feature_obj(std::get<std::shared_ptr<Features>>(features->ptr)))
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Failed to append features");
+		return {RETURN_CODE::SUCCESS, nullptr};
+	});
+}
+
+Result combined_kernel_append(sgobject_t* combined, const sgobject_t* kernel) {
+	if (auto result = check_type<Kernel, Result>(combined, "Expected self to be of type Kernel"))
+		return *result;
+	if (auto result = check_type<Kernel, Result>(kernel, "Expected to append a Kernel"))
+		return *result;
+	return exception_firewall<Result>([&]() -> Result {
+		auto lock = combined->write_lock({kernel});
+		auto combined_kernel = std::dynamic_pointer_cast<CombinedKernel>(std::get<std::shared_ptr<Kernel>>(combined->ptr));
+		if (!combined_kernel)
+			throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Expected self to be a CombinedKernel");
+		if (!combined_kernel->append_kernel(std::get<std::shared_ptr<Kernel>>(kernel->ptr)))
+			throw ShimException(ERROR_CODE::INVALID_INPUT, "Failed to append kernel");
+		return {RETURN_CODE::SUCCESS, nullptr};
+	});
+}
+
+cvisitor_result get_subkernel_weights(const sgobject_t* ptr) {
+	if (auto result = check_type<Kernel, cvisitor_result>(ptr, "Expected self to be of type Kernel"))
+		return *result;
+	return exception_firewall<cvisitor_result>([&]() -> cvisitor_result {
+		// the weights are cached in the kernel when they are read
+		auto lock = ptr->write_lock();
+		auto combined_kernel = std::dynamic_pointer_cast<CombinedKernel>(std::get<std::shared_ptr<Kernel>>(ptr->ptr));
+		if (!combined_kernel)
+			throw ShimException(ERROR_CODE::TYPE_MISMATCH, "Expected self to be a CombinedKernel");
+		const auto weights = combined_kernel->get_subkernel_weights();
+		auto* result = new float64_t[weights.vlen];
+		sg_memcpy(result, weights.vector, weights.vlen * sizeof(float64_t));
+		auto visitor = std::make_unique<C_Visitor>();
+		visitor->m_type = get_type<float64_t>::type;
+		visitor->m_container = CONTAINER_TYPE::CT_VECTOR;
+		visitor->m_rows = weights.vlen;
+		visitor->m_value = (void*)result;
+		visitor->m_deleter = [](void* value) { delete[] static_cast<float64_t*>(value); };
+		return {RETURN_CODE::SUCCESS, visitor.release()};
+	});
 }
 
 sgobject_result create_features_from_file(const sgobject_t* file) {
 	if (auto result = check_type<File>(file, "Expected self to be of type File"))
 		return *result;
-	return create_helper<Features>(std::get<std::shared_ptr<File>>(file->ptr));
+	return exception_firewall([&]() -> sgobject_result {
+		// reading advances the position in the file
+		auto lock = file->write_lock();
+		return create_helper<Features>(std::get<std::shared_ptr<File>>(file->ptr));
+	});
 }
 
 sgobject_result create_labels(const char* name) {
-	sgobject_result result;
-	result.return_code = RETURN_CODE::ERROR;
-	result.result.error = "Cannot generate a Labels instance from class name";
-	return result;
+	return error_result(ERROR_CODE::INVALID_INPUT, "Cannot generate a Labels instance from class name");
 }
 
 sgobject_result create_labels_from_file(const sgobject_t* file) {
 	if (auto result = check_type<File>(file, "Expected self to be of type File"))
 		return *result;
-	return create_helper<Labels>(std::get<std::shared_ptr<File>>(file->ptr));
+	return exception_firewall([&]() -> sgobject_result {
+		// reading advances the position in the file
+		auto lock = file->write_lock();
+		return create_helper<Labels>(std::get<std::shared_ptr<File>>(file->ptr));
+	});
 }
 
 Result init_kernel(sgobject_t* kernel, sgobject_t* lhs, sgobject_t* rhs) {
 	if (!std::holds_alternative<std::shared_ptr<Kernel>>(kernel->ptr)) {
-		return Result{RETURN_CODE::ERROR, "Expected self to be Kernel type."};
+		return error_result<Result>(ERROR_CODE::INVALID_INPUT, "Expected self to be Kernel type.");
 	}
 	if (!std::holds_alternative<std::shared_ptr<Features>>(lhs->ptr)) {
-		return Result{RETURN_CODE::ERROR, "Expected lhs to be of type Features"};
+		return error_result<Result>(ERROR_CODE::INVALID_INPUT, "Expected lhs to be of type Features");
 	}
 	if (!std::holds_alternative<std::shared_ptr<Features>>(rhs->ptr)) {
-		return Result{RETURN_CODE::ERROR, "Expected rhs to be of type Features"};
+		return error_result<Result>(ERROR_CODE::INVALID_INPUT, "Expected rhs to be of type Features");
 	}
-	std::get<std::shared_ptr<Kernel>>(kernel->ptr)->init(
-		std::get<std::shared_ptr<Features>>(lhs->ptr),
-		std::get<std::shared_ptr<Features>>(rhs->ptr)
-	);
-	return {RETURN_CODE::SUCCESS, nullptr};
+	return exception_firewall<Result>([&]() -> Result {
+		auto lock = kernel->write_lock({lhs, rhs});
+		std::get<std::shared_ptr<Kernel>>(kernel->ptr)->init(
+			std::get<std::shared_ptr<Features>>(lhs->ptr),
+			std::get<std::shared_ptr<Features>>(rhs->ptr)
+		);
+		return {RETURN_CODE::SUCCESS, nullptr};
+	});
 }
 
 sgobject_result create_file(const char* name) {
-	return create_helper<File>(name);
+	return create_from_name_helper<File>(name);
 }
 
 sgobject_result read_csvfile(const char* filepath) {
@@ -502,11 +1638,11 @@ sgobject_result read_csvfile(const char* filepath) {
 }
 
 sgobject_result create_combination_rule(const char* name) {
-	return create_helper<CombinationRule>(name);
+	return create_from_name_helper<CombinationRule>(name);
 }
 
 sgobject_result create_evaluation(const char* name) {
-	return create_helper<Evaluation>(name);
+	return create_from_name_helper<Evaluation>(name);
 }
 
 float64_result evaluate_labels(sgobject* self, sgobject_t* y_pred, sgobject_t* y_true) {
@@ -516,9 +1652,12 @@ float64_result evaluate_labels(sgobject* self, sgobject_t* y_pred, sgobject_t* y
 		return *result;
 	if (auto result = check_type<Labels, float64_result>(y_true, "Expected y_true to be of type Labels"))
 		return *result;
-	auto result = std::get<std::shared_ptr<Evaluation>>(self->ptr)->evaluate(
-		std::get<std::shared_ptr<Labels>>(y_pred->ptr),
-		std::get<std::shared_ptr<Labels>>(y_true->ptr)
-	);
-	return {RETURN_CODE::SUCCESS, result};
+	return exception_firewall<float64_result>([&]() -> float64_result {
+		auto lock = self->write_lock({y_pred, y_true});
+		auto result = std::get<std::shared_ptr<Evaluation>>(self->ptr)->evaluate(
+			std::get<std::shared_ptr<Labels>>(y_pred->ptr),
+			std::get<std::shared_ptr<Labels>>(y_true->ptr)
+		);
+		return {RETURN_CODE::SUCCESS, result};
+	});
 }
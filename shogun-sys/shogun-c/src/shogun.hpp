@@ -4,13 +4,27 @@ extern "C" {
 	struct version;
 	struct sgobject;
 	struct C_Visitor;
+	struct parameter_list;
+	struct string_list;
+	struct sparse_matrix;
 
 	typedef struct version version_t;
 	typedef struct sgobject sgobject_t;
 	typedef struct C_Visitor cvisitor_t;
+	typedef struct parameter_list parameter_list_t;
+	typedef struct string_list string_list_t;
+	typedef struct sparse_matrix sparse_matrix_t;
 	
 	enum RETURN_CODE {SUCCESS, ERROR};
 
+	enum ERROR_CODE {
+		NATIVE_EXCEPTION,
+		UNKNOWN_CLASS,
+		PARAMETER_NOT_FOUND,
+		TYPE_MISMATCH,
+		INVALID_INPUT,
+	};
+
 	struct sgobject_result
 	{
 		RETURN_CODE return_code;
@@ -19,6 +33,7 @@ extern "C" {
 			sgobject_t* result;
 			const char* error;
 		} result;
+		ERROR_CODE error_code;
 	};
 
 	struct float64_result
@@ -29,12 +44,80 @@ extern "C" {
 			double result;
 			const char* error;
 		} result;
+		ERROR_CODE error_code;
+	};
+
+	struct bool_result
+	{
+		RETURN_CODE return_code;
+		union ResultBoolUnion
+		{
+			bool result;
+			const char* error;
+		} result;
+		ERROR_CODE error_code;
+	};
+
+	struct uint64_result
+	{
+		RETURN_CODE return_code;
+		union ResultUInt64Union
+		{
+			uint64_t result;
+			const char* error;
+		} result;
+		ERROR_CODE error_code;
+	};
+
+	struct string_result
+	{
+		RETURN_CODE return_code;
+		union ResultStringUnion
+		{
+			const char* result;
+			const char* error;
+		} result;
+		ERROR_CODE error_code;
+	};
+
+	struct cvisitor_result
+	{
+		RETURN_CODE return_code;
+		union ResultCVisitorUnion
+		{
+			cvisitor_t* result;
+			const char* error;
+		} result;
+		ERROR_CODE error_code;
+	};
+
+	struct parameter_list_result
+	{
+		RETURN_CODE return_code;
+		union ResultParameterListUnion
+		{
+			parameter_list_t* result;
+			const char* error;
+		} result;
+		ERROR_CODE error_code;
+	};
+
+	struct sparse_matrix_result
+	{
+		RETURN_CODE return_code;
+		union ResultSparseMatrixUnion
+		{
+			sparse_matrix_t* result;
+			const char* error;
+		} result;
+		ERROR_CODE error_code;
 	};
 
 	struct Result
 	{
 		RETURN_CODE return_code;
 		const char* error;
+		ERROR_CODE error_code;
 	};
 	
 
@@ -44,6 +127,31 @@ extern "C" {
 		FLOAT32,
 		FLOAT64,
 		SGOBJECT,
+		BOOL,
+		UINT32,
+		UINT64,
+		CHAR,
+		// null terminated char array
+		STRING,
+		// name of an enum option, passed like STRING
+		ENUM,
+	};
+
+	struct type_result
+	{
+		RETURN_CODE return_code;
+		union ResultTypeUnion
+		{
+			TYPE result;
+			const char* error;
+		} result;
+		ERROR_CODE error_code;
+	};
+
+	enum CONTAINER_TYPE {
+		CT_SCALAR,
+		CT_VECTOR,
+		CT_MATRIX,
 	};
 	
 	enum SG_TYPE {
@@ -57,9 +165,64 @@ extern "C" {
 		SG_EVALUATION,
 	};
 
+	enum SERIALIZATION_FORMAT {
+		JSON,
+		BINARY,
+	};
+
+	enum FEATURE_CLASS {
+		FC_DENSE,
+		FC_SPARSE,
+		FC_STRING,
+		FC_COMBINED,
+		FC_OTHER,
+	};
+
+	// alphabets of string features
+	enum ALPHABET {
+		AL_DNA,
+		AL_RAWDNA,
+		AL_RNA,
+		AL_PROTEIN,
+		AL_BINARY,
+		AL_ALPHANUM,
+		AL_CUBE,
+		AL_RAWBYTE,
+		AL_IUPAC_NUCLEIC_ACID,
+		AL_IUPAC_AMINO_ACID,
+		AL_DIGIT,
+		AL_DIGIT2,
+		AL_RAWDIGIT,
+		AL_RAWDIGIT2,
+		AL_SNP,
+		AL_RAWSNP,
+	};
+
+	enum PARAMETER_PROPERTY {
+		HYPERPARAMETER = 1,
+		GRADIENT_PARAMETER = 2,
+		MODEL_PARAMETER = 4,
+		// cannot be set with put
+		READONLY_PARAMETER = 8,
+	};
+
 	TYPE get_cvisitor_type(const cvisitor_t*);
+	CONTAINER_TYPE get_cvisitor_container(const cvisitor_t*);
 	const char* get_cvisitor_typename(const cvisitor_t*);
 	void* get_cvisitor_pointer(const cvisitor_t*);
+	void* release_cvisitor_pointer(cvisitor_t*);
+	void destroy_cvisitor(cvisitor_t*);
+	uint32_t get_cvisitor_rows(const cvisitor_t*);
+	uint32_t get_cvisitor_cols(const cvisitor_t*);
+
+	void free_string(const char*);
+	int64_t get_live_allocations();
+
+	void destroy_string_list(string_list_t*);
+	uint32_t get_string_list_size(const string_list_t*);
+	const char* get_string_list_element(const string_list_t*, uint32_t);
+
+	string_list_t* available_classes();
 
 	version_t* create_version();
 	void destroy_version(version_t*);
@@ -67,10 +230,27 @@ extern "C" {
 
 	void destroy_sgobject(sgobject_t*);
 	const char* to_string(const sgobject_t*);
-	cvisitor_t* sgobject_get(const sgobject_t*, const char*);
+	const char* sgobject_class_name(const sgobject_t*);
+	cvisitor_result sgobject_get(const sgobject_t*, const char*);
 	Result sgobject_put(sgobject_t*, const char*, const void*, TYPE);
-	Result sgobject_put_array(sgobject_t*, const char*, const void*, uint32_t, uint32_t, TYPE);
+	Result sgobject_put_array(sgobject_t*, const char*, const void*, uint32_t, uint32_t, TYPE, CONTAINER_TYPE);
 	SG_TYPE sgobject_derived_type(const sgobject_t*);
+	sgobject_result sgobject_clone(const sgobject_t*);
+	sgobject_result sgobject_share(const sgobject_t*);
+	bool_result sgobject_equals(const sgobject_t*, const sgobject_t*);
+
+	string_result sgobject_to_json(const sgobject_t*);
+	sgobject_result sgobject_from_json(const char*);
+	Result sgobject_save(const sgobject_t*, const char*, SERIALIZATION_FORMAT);
+	sgobject_result sgobject_load(const char*, SERIALIZATION_FORMAT);
+
+	parameter_list_result sgobject_parameters(const sgobject_t*);
+	void destroy_parameter_list(parameter_list_t*);
+	uint32_t get_parameter_list_size(const parameter_list_t*);
+	const char* get_parameter_list_name(const parameter_list_t*, uint32_t);
+	const char* get_parameter_list_typename(const parameter_list_t*, uint32_t);
+	const char* get_parameter_list_description(const parameter_list_t*, uint32_t);
+	uint32_t get_parameter_list_properties(const parameter_list_t*, uint32_t);
 
 	sgobject_result create_machine(const char*);
 	Result train_machine(sgobject_t*, sgobject_t*);
@@ -85,7 +265,31 @@ extern "C" {
 
 	sgobject_result create_features(const char*);
 	sgobject_result create_features_from_data(const void*, uint32_t rows, uint32_t cols, TYPE);
+	sgobject_result create_features_from_borrowed_data(void*, uint32_t rows, uint32_t cols, TYPE);
+	// compressed sparse matrix with one sample per outer dimension
+	sgobject_result create_sparse_features(const uint64_t* indptr, const uint64_t* indices, const void* data, uint32_t num_features, uint32_t num_vectors, TYPE);
+	sparse_matrix_result get_sparse_feature_matrix(const sgobject_t*);
+	void destroy_sparse_matrix(sparse_matrix_t*);
+	TYPE get_sparse_matrix_type(const sparse_matrix_t*);
+	uint32_t get_sparse_matrix_num_features(const sparse_matrix_t*);
+	uint32_t get_sparse_matrix_num_vectors(const sparse_matrix_t*);
+	uint64_t get_sparse_matrix_nnz(const sparse_matrix_t*);
+	const uint64_t* get_sparse_matrix_indptr(const sparse_matrix_t*);
+	const uint64_t* get_sparse_matrix_indices(const sparse_matrix_t*);
+	const void* get_sparse_matrix_data(const sparse_matrix_t*);
 	sgobject_result create_features_from_file(const sgobject_t*);
+	// string i is data[offsets[i]..offsets[i + 1]]
+	sgobject_result create_string_features(const char* data, const uint64_t* offsets, uint32_t num_strings, ALPHABET);
+	uint64_result get_features_num_vectors(const sgobject_t*);
+	uint64_result get_features_num_features(const sgobject_t*);
+	FEATURE_CLASS get_features_class(const sgobject_t*);
+	type_result get_features_type(const sgobject_t*);
+	// subsets of Features or Labels, which can be nested
+	Result add_subset(sgobject_t*, const uint64_t* indices, uint64_t num_indices);
+	Result remove_subset(sgobject_t*);
+	Result combined_features_append(sgobject_t* combined, const sgobject_t* features);
+	Result combined_kernel_append(sgobject_t* combined, const sgobject_t* kernel);
+	cvisitor_result get_subkernel_weights(const sgobject_t*);
 
 	sgobject_result create_labels(const char*);
 	sgobject_result create_labels_from_file(const sgobject_t*);
@@ -98,5 +302,5 @@ extern "C" {
 	sgobject_result create_evaluation(const char*);
 	float64_result evaluate_labels(sgobject_t*, sgobject_t*, sgobject_t*);
 
-	void set_parallel_threads(int32_t);
+	Result set_parallel_threads(int32_t);
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
-use shogun::shogun::{Distance, Kernel, Machine, Version, Features, SGObject};
+use shogun::shogun::{Distance, Kernel, Machine, Version, Features, SGObject, ShogunError};
 use ndarray::arr2;
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), ShogunError> {
     let version = Version::new();
     println!("Shogun version {}", version.main_version()?);
 
@@ -10,33 +10,23 @@ fn main() -> Result<(), String> {
 
     let mut gaussian = Kernel::new("GaussianKernel")?;
     println!("{}", gaussian);
-    match gaussian.get("log_width") {
-        Ok(value) => match value.downcast_ref::<f64>() {
-            Some(fvalue) => println!("log_width: {}", fvalue),
-            None => println!("log_width not f64"),
-        },
-        Err(msg) => panic!(msg),
-    }
+    let log_width = gaussian.get::<f64>("log_width")?;
+    println!("log_width: {}", log_width);
 
-    match gaussian.get("cache_size") {
-        Ok(value) => match value.downcast_ref::<i32>() {
-            Some(fvalue) => println!("cache_size: {}", fvalue),
-            None => println!("cache_size not i32"),
-        },
-        Err(msg) => panic!(msg),
-    }
+    let cache_size = gaussian.get::<i32>("cache_size")?;
+    println!("cache_size: {}", cache_size);
 
-    match gaussian.get("m_distance") {
-        Ok(value) => match value.downcast_ref::<Distance>() {
-            Some(fvalue) => println!("m_distance: {}", fvalue),
-            None => println!("m_distance not Distance"),
-        },
-        Err(msg) => panic!(msg),
+    let distance = gaussian.get::<Distance>("m_distance")?;
+    println!("m_distance: {}", distance);
+
+    match gaussian.get::<i32>("log_width") {
+        Ok(_) => println!("log_width is i32"),
+        Err(msg) => println!("{}", msg),
     }
 
     match Machine::new("RandomForests") {
         Ok(_) => println!("All good"),
-        Err(msg) => println!("ShogunException: {}", msg),
+        Err(msg) => println!("{}", msg),
     }
 
     match gaussian.put("log_width", &1.0) {
@@ -49,7 +39,7 @@ fn main() -> Result<(), String> {
         _ => (),
     }
         
-    match gaussian.get("log_width") {
+    match gaussian.get_any("log_width") {
         Ok(value) => match value.downcast_ref::<f64>() {
             Some(fvalue) => println!("log_width: {}", fvalue),
             None => println!("log_width not f64"),
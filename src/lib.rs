@@ -4,39 +4,284 @@ pub mod shogun {
 
     mod details {
         use std::ffi::CStr;
-        pub fn sgobject_to_string<T>(obj: *const T) -> &'static str {
-            let c_repr =
-                unsafe { shogun_sys::to_string(obj as *const _ as *const shogun_sys::sgobject_t) };
-            let repr = unsafe { CStr::from_ptr(c_repr) };
-            repr.to_str()
-                .expect("Failed to get SGObject representation")
+        use super::ShogunError;
+
+        pub fn sgobject_to_string<T>(obj: *const T) -> String {
+            unsafe {
+                let c_repr = shogun_sys::to_string(obj as *const _ as *const shogun_sys::sgobject_t);
+                let repr = CStr::from_ptr(c_repr).to_str()
+                    .expect("Failed to get SGObject representation")
+                    .to_string();
+                shogun_sys::free_string(c_repr);
+                repr
+            }
         }
 
-        pub fn handle_result(result: &shogun_sys::Result) -> Result<(), String> {
+        pub fn sgobject_class_name(obj: *const shogun_sys::sgobject_t) -> String {
             unsafe {
-                match result {
-                    shogun_sys::Result {
-                        return_code: shogun_sys::RETURN_CODE_ERROR,
-                        error: msg,
-                    } => {
-                        let c_error_str = CStr::from_ptr(*msg);
-                        Err(format!("{}", c_error_str.to_str().expect("Failed to get error")))
-                    }
-                    shogun_sys::Result {
-                        return_code: shogun_sys::RETURN_CODE_SUCCESS,
-                        error: _,
-                    } => Ok(()),
-                    _ => Err("Unexpected return.".to_string())
+                let c_name = shogun_sys::sgobject_class_name(obj);
+                let name = CStr::from_ptr(c_name).to_str()
+                    .expect("Failed to get SGObject class name")
+                    .to_string();
+                shogun_sys::free_string(c_name);
+                name
+            }
+        }
+
+        /// Maps an error code and message from the C API to a ShogunError,
+        /// the message is released
+        pub fn error_from_c(error_code: shogun_sys::ERROR_CODE, c_msg: *const std::os::raw::c_char) -> ShogunError {
+            let msg = unsafe {
+                let msg = CStr::from_ptr(c_msg).to_str().expect("Failed to get error").to_string();
+                shogun_sys::free_string(c_msg);
+                msg
+            };
+            match error_code {
+                shogun_sys::ERROR_CODE_UNKNOWN_CLASS => ShogunError::UnknownClass(msg),
+                shogun_sys::ERROR_CODE_PARAMETER_NOT_FOUND => ShogunError::ParameterNotFound(msg),
+                shogun_sys::ERROR_CODE_TYPE_MISMATCH => ShogunError::TypeMismatch(msg),
+                shogun_sys::ERROR_CODE_INVALID_INPUT => ShogunError::InvalidInput(msg),
+                _ => ShogunError::NativeException(msg),
+            }
+        }
+
+        /// Copies a string list from the C API and destroys it, a null list is treated as empty
+        pub fn string_list_to_vec(c_list: *mut shogun_sys::string_list_t) -> Vec<String> {
+            if c_list.is_null() {
+                return Vec::new();
+            }
+            unsafe {
+                let strings = (0..shogun_sys::get_string_list_size(c_list)).map(|idx| {
+                    let c_str = CStr::from_ptr(shogun_sys::get_string_list_element(c_list, idx));
+                    c_str.to_str().expect("Failed to get string").to_string()
+                }).collect();
+                shogun_sys::destroy_string_list(c_list);
+                strings
+            }
+        }
+
+        fn edit_distance(lhs: &str, rhs: &str) -> usize {
+            let rhs: Vec<char> = rhs.chars().collect();
+            let mut previous: Vec<usize> = (0..=rhs.len()).collect();
+            for (i, lhs_c) in lhs.chars().enumerate() {
+                let mut current = vec![i + 1; rhs.len() + 1];
+                for (j, rhs_c) in rhs.iter().enumerate() {
+                    let substitution = previous[j] + if lhs_c == *rhs_c { 0 } else { 1 };
+                    current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+                }
+                previous = current;
+            }
+            previous[rhs.len()]
+        }
+
+        /// Adds a "did you mean" hint to an unknown class error if one of
+        /// the available classes is close enough to the requested name.
+        /// The classes are only listed for unknown class errors.
+        pub fn suggest_class<F>(error: ShogunError, name: &str, available_classes: F) -> ShogunError
+        where F: FnOnce() -> Vec<String> {
+            let msg = match error {
+                ShogunError::UnknownClass(msg) => msg,
+                error => return error,
+            };
+            let lower_name = name.to_lowercase();
+            let closest = available_classes().into_iter()
+                .map(|class| (edit_distance(&lower_name, &class.to_lowercase()), class))
+                .min();
+            match closest {
+                Some((distance, class)) if distance <= std::cmp::max(2, name.len() / 3) => {
+                    ShogunError::UnknownClass(format!("{} Did you mean {}?", msg, class))
+                },
+                _ => ShogunError::UnknownClass(msg),
+            }
+        }
+
+        /// Returns the matrix with the column-major memory layout of SGMatrix,
+        /// only copying if the view is not already Fortran contiguous
+        pub fn to_column_major<T: Clone>(matrix: ndarray::ArrayView2<T>) -> ndarray::CowArray<T, ndarray::Ix2> {
+            let transposed = matrix.reversed_axes();
+            if transposed.is_standard_layout() {
+                ndarray::CowArray::from(transposed)
+            } else {
+                ndarray::CowArray::from(transposed.as_standard_layout().into_owned())
+            }
+        }
+
+        pub fn handle_result(result: &shogun_sys::Result) -> Result<(), ShogunError> {
+            match result.return_code {
+                shogun_sys::RETURN_CODE_SUCCESS => Ok(()),
+                shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.error)),
+                _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
+            }
+        }
+
+        pub fn handle_string_result(result: &shogun_sys::string_result) -> Result<String, ShogunError> {
+            unsafe {
+                match result.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => {
+                        let c_str = result.result.result;
+                        let string = CStr::from_ptr(c_str).to_string_lossy().into_owned();
+                        shogun_sys::free_string(c_str);
+                        Ok(string)
+                    },
+                    shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
+                }
+            }
+        }
+
+        /// Error for a visitor without a value, which is either an unset
+        /// SGObject parameter or a type the C API cannot hand out
+        pub fn null_value_error(c_visitor: *mut shogun_sys::cvisitor_t, parameter_name: &str) -> ShogunError {
+            if unsafe { shogun_sys::get_cvisitor_type(c_visitor) } == shogun_sys::TYPE_SGOBJECT {
+                unset_parameter_error(parameter_name)
+            } else {
+                ShogunError::TypeMismatch(format!("Cannot handle the type of parameter {}", parameter_name))
+            }
+        }
+
+        /// Error for reading an object parameter that does not point to an
+        /// object
+        pub fn unset_parameter_error(parameter_name: &str) -> ShogunError {
+            ShogunError::InvalidInput(format!("Parameter {} is not set", parameter_name))
+        }
+
+        /// Names of classes, parameters and files cannot contain null bytes
+        pub fn name_to_cstring(name: &str) -> Result<std::ffi::CString, ShogunError> {
+            std::ffi::CString::new(name)
+                .map_err(|_| ShogunError::InvalidInput(format!("Name {:?} contains a null byte", name)))
+        }
+
+        /// Shogun indexes arrays with 32 bit integers, what describes the
+        /// counted elements, e.g. rows
+        pub fn to_u32(value: usize, what: &str) -> Result<u32, ShogunError> {
+            use std::convert::TryFrom;
+            u32::try_from(value)
+                .map_err(|_| ShogunError::InvalidInput(format!("Too many {} for Shogun: {}", what, value)))
+        }
+
+        pub fn path_to_cstring(path: &std::path::Path) -> Result<std::ffi::CString, ShogunError> {
+            path.to_str()
+                .and_then(|path| std::ffi::CString::new(path).ok())
+                .ok_or_else(|| ShogunError::InvalidInput(format!("Cannot pass path {} to Shogun", path.display())))
+        }
+
+        /// Checks that a deserialized object has the expected base type,
+        /// the object is released otherwise
+        pub fn expect_derived_type<T>(obj: T, expected: shogun_sys::SG_TYPE, type_name: &str) -> Result<T, ShogunError>
+        where T: super::SGObject {
+            if unsafe { shogun_sys::sgobject_derived_type(obj.get_ptr()) } == expected {
+                Ok(obj)
+            } else {
+                Err(ShogunError::TypeMismatch(format!("expected {}, got {}", type_name, obj.class_name())))
+            }
+        }
+
+        pub fn handle_bool_result(result: &shogun_sys::bool_result) -> Result<bool, ShogunError> {
+            unsafe {
+                match result.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => Ok(result.result.result),
+                    shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
+                }
+            }
+        }
+
+        pub fn handle_uint64_result(result: &shogun_sys::uint64_result) -> Result<u64, ShogunError> {
+            unsafe {
+                match result.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => Ok(result.result.result),
+                    shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
+                }
+            }
+        }
+
+        pub fn handle_type_result(result: &shogun_sys::type_result) -> Result<shogun_sys::TYPE, ShogunError> {
+            unsafe {
+                match result.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => Ok(result.result.result),
+                    shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
+                }
+            }
+        }
+
+        pub fn handle_cvisitor_result(result: &shogun_sys::cvisitor_result) -> Result<*mut shogun_sys::cvisitor_t, ShogunError> {
+            unsafe {
+                match result.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => Ok(result.result.result),
+                    shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
+                }
+            }
+        }
+
+        #[cfg(feature = "sprs")]
+        pub fn handle_sparse_matrix_result(result: &shogun_sys::sparse_matrix_result) -> Result<*mut shogun_sys::sparse_matrix_t, ShogunError> {
+            unsafe {
+                match result.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => Ok(result.result.result),
+                    shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
+                }
+            }
+        }
+
+        pub fn handle_parameter_list_result(result: &shogun_sys::parameter_list_result) -> Result<*mut shogun_sys::parameter_list_t, ShogunError> {
+            unsafe {
+                match result.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => Ok(result.result.result),
+                    shogun_sys::RETURN_CODE_ERROR => Err(error_from_c(result.error_code, result.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string())),
                 }
             }
         }
     }
 
-    use shogun_rust_procedural::{SGObject, getter_reflection};
+    use shogun_rust_procedural::{SGObject, getter_reflection, any_sgobject, typed_wrappers};
     use std::ffi::{CStr, CString};
     use std::fmt;
+    use std::error::Error;
+    use std::marker::PhantomData;
+    use std::path::Path;
+    use std::ops::Deref;
     extern crate ndarray;
-    use ndarray::Array2;
+    use ndarray::{Array1, Array2, ArrayView2, ArrayViewMut2, AsArray, Ix2, ShapeBuilder};
+
+    /// Errors returned by the Shogun bindings
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ShogunError {
+        /// The class name is not known to Shogun's class registry
+        UnknownClass(String),
+        /// The object has no parameter with the requested name
+        ParameterNotFound(String),
+        /// The value does not have the type the parameter or caller expected
+        TypeMismatch(String),
+        /// An exception was thrown by the Shogun library
+        NativeException(String),
+        /// The arguments passed to Shogun are invalid
+        InvalidInput(String),
+        /// Several operations failed, e.g. the puts of a builder
+        Multiple(Vec<ShogunError>),
+    }
+
+    impl fmt::Display for ShogunError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ShogunError::UnknownClass(msg) => write!(f, "Unknown class: {}", msg),
+                ShogunError::ParameterNotFound(msg) => write!(f, "Parameter not found: {}", msg),
+                ShogunError::TypeMismatch(msg) => write!(f, "Type mismatch: {}", msg),
+                ShogunError::NativeException(msg) => write!(f, "ShogunException: {}", msg),
+                ShogunError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+                ShogunError::Multiple(errors) => {
+                    let msgs: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+                    write!(f, "{}", msgs.join("; "))
+                },
+            }
+        }
+    }
+
+    impl Error for ShogunError {}
 
     /// Struct owns a *mut shogun_sys::sgobject
     pub trait HasSGObjectPtr {
@@ -44,45 +289,190 @@ pub mod shogun {
     }
 
     /// The trait that all SGObject derived types have to implement
+    ///
+    /// Handles are `Send` and `Sync`. Operations that modify the object,
+    /// such as `put`, `Machine::train` and `Kernel::init`, take `&mut self`,
+    /// all others take `&self`. Handles created with `share` or returned by
+    /// `get` refer to the same native object, and objects reach each other
+    /// through their parameters, so the C API locks every object reachable
+    /// from the arguments of an operation: reads of the same objects run
+    /// concurrently, modifications are exclusive, and operations on
+    /// unrelated objects do not wait for each other. `Machine::apply`
+    /// updates the machine while predicting, so predictions with the same
+    /// machine run one at a time; predict with a `deep_clone` per thread
+    /// to run them in parallel.
     pub trait SGObject: fmt::Display + HasSGObjectPtr {
         /// The SGObject derived type
         type DerivedObject;
         /// Factory to generate new DerivedObject types from a string
-        fn create(name: &'static str) -> Result<Self::DerivedObject, String>;
+        fn create(name: &str) -> Result<Self::DerivedObject, ShogunError>;
         /// Setter for any type that implements the SGObjectPut trait 
-        fn put<T>(&self, parameter_name: &'static str, parameter_value: &T) -> Result<(), String>
-        where T: SGObjectPut {
-            parameter_value.sgobject_put(self.get_ptr(), parameter_name)
+        fn put<T>(&mut self, parameter_name: &str, parameter_value: &T) -> Result<(), ShogunError>
+        where T: SGObjectPut + ?Sized {
+            // the pointer of a live handle is valid
+            unsafe { parameter_value.sgobject_put(self.get_ptr(), parameter_name) }
         }
         getter_reflection!{}
+        /// Lists all registered parameters with their current value when
+        /// it can be represented in Rust
+        fn parameters(&self) -> Result<Vec<ParameterDescriptor>, ShogunError> {
+            unsafe {
+                let c_list = details::handle_parameter_list_result(&shogun_sys::sgobject_parameters(self.get_ptr()))?;
+                let c_str = |ptr| CStr::from_ptr(ptr).to_str().expect("Failed to get parameter info").to_string();
+                let parameters: Result<Vec<ParameterDescriptor>, ShogunError> = (0..shogun_sys::get_parameter_list_size(c_list)).map(|idx| {
+                    let name = c_str(shogun_sys::get_parameter_list_name(c_list, idx));
+                    let properties = shogun_sys::get_parameter_list_properties(c_list, idx);
+                    Ok(ParameterDescriptor {
+                        type_name: c_str(shogun_sys::get_parameter_list_typename(c_list, idx)),
+                        description: c_str(shogun_sys::get_parameter_list_description(c_list, idx)),
+                        hyperparameter: properties & shogun_sys::PARAMETER_PROPERTY_HYPERPARAMETER != 0,
+                        gradient: properties & shogun_sys::PARAMETER_PROPERTY_GRADIENT_PARAMETER != 0,
+                        model_parameter: properties & shogun_sys::PARAMETER_PROPERTY_MODEL_PARAMETER != 0,
+                        read_only: properties & shogun_sys::PARAMETER_PROPERTY_READONLY_PARAMETER != 0,
+                        owner: AnySGObject::share_of(self)?,
+                        name,
+                    })
+                }).collect();
+                shogun_sys::destroy_parameter_list(c_list);
+                parameters
+            }
+        }
+        /// Independent copy of the object and its parameters, made with
+        /// Shogun's clone
+        fn deep_clone(&self) -> Result<Self::DerivedObject, ShogunError>;
+        /// Another handle to the same native object, so changes made through
+        /// either handle are visible through both
+        fn share(&self) -> Result<Self::DerivedObject, ShogunError>;
+        /// Compares the class and all registered parameters of both objects
+        /// with Shogun's equals
+        fn equals<T>(&self, other: &T) -> Result<bool, ShogunError>
+        where T: SGObject {
+            unsafe { details::handle_bool_result(&shogun_sys::sgobject_equals(self.get_ptr(), other.get_ptr())) }
+        }
+        /// Name of the Shogun class, e.g. GaussianKernel for a Kernel
+        fn class_name(&self) -> String {
+            details::sgobject_class_name(self.get_ptr())
+        }
+        /// Serializes the object and all its parameters with Shogun's JSON serializer
+        fn to_json(&self) -> Result<String, ShogunError> {
+            details::handle_string_result(unsafe { &shogun_sys::sgobject_to_json(self.get_ptr()) })
+        }
+        /// Restores an object serialized with to_json
+        fn from_json(json: &str) -> Result<Self::DerivedObject, ShogunError>;
+        /// Saves the object to a JSON file
+        fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ShogunError> {
+            self.save_as(path, SerializationFormat::Json)
+        }
+        /// Saves the object to a file in the given format
+        fn save_as<P: AsRef<Path>>(&self, path: P, format: SerializationFormat) -> Result<(), ShogunError> {
+            let c_path = details::path_to_cstring(path.as_ref())?;
+            details::handle_result(unsafe { &shogun_sys::sgobject_save(self.get_ptr(), c_path.as_ptr(), format.to_c()) })
+        }
+        /// Loads an object saved with save
+        fn load<P: AsRef<Path>>(path: P) -> Result<Self::DerivedObject, ShogunError> {
+            Self::load_as(path, SerializationFormat::Json)
+        }
+        /// Loads an object saved with save_as, fails if the file holds an
+        /// object of another base type
+        fn load_as<P: AsRef<Path>>(path: P, format: SerializationFormat) -> Result<Self::DerivedObject, ShogunError>;
         /// String representation of the struct
-        fn to_string(&self) -> &str;
+        fn to_string(&self) -> String;
+    }
+
+    /// File formats of Shogun's serializers
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum SerializationFormat {
+        Json,
+        /// Only available if Shogun was built with its binary serializer
+        Binary,
+    }
+
+    impl SerializationFormat {
+        fn to_c(self) -> shogun_sys::SERIALIZATION_FORMAT {
+            match self {
+                SerializationFormat::Json => shogun_sys::SERIALIZATION_FORMAT_JSON,
+                SerializationFormat::Binary => shogun_sys::SERIALIZATION_FORMAT_BINARY,
+            }
+        }
+    }
+
+    /// Description of a parameter registered in an SGObject
+    #[derive(Debug)]
+    pub struct ParameterDescriptor {
+        /// Name used to put and get the parameter
+        pub name: String,
+        /// Name of the Shogun (C++) type of the parameter
+        pub type_name: String,
+        pub description: String,
+        pub hyperparameter: bool,
+        pub gradient: bool,
+        pub model_parameter: bool,
+        /// Whether the parameter cannot be set with put
+        pub read_only: bool,
+        owner: AnySGObject,
+    }
+
+    impl ParameterDescriptor {
+        /// Whether the parameter configures the object, i.e. it can be put
+        /// and is not learned while training
+        pub fn settable(&self) -> bool {
+            !self.read_only && !self.model_parameter
+        }
+
+        /// Current value of the parameter, which is only read when this is
+        /// called since vectors and matrices are copied. Fails if the type
+        /// cannot be represented in Rust.
+        pub fn value(&self) -> Result<Box<dyn std::any::Any>, ShogunError> {
+            self.owner.get_any(&self.name)
+        }
     }
 
     pub trait SGObjectFromPtr {
         type DerivedObject;
-        fn from_ptr(ptr: *mut shogun_sys::sgobject) -> Self::DerivedObject;
+        /// Wraps a pointer returned by the C API in a handle, which
+        /// destroys it when it is dropped
+        ///
+        /// # Safety
+        ///
+        /// ptr has to point to a live native object that is not owned by
+        /// another handle.
+        unsafe fn from_ptr(ptr: *mut shogun_sys::sgobject) -> Self::DerivedObject;
     }
 
     /// Trait for types that can be put in an SGObject 
     pub trait SGObjectPut {
-        fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, name: &'static str) -> Result<(), String>;
+        /// Puts the value in parameter name of obj
+        ///
+        /// # Safety
+        ///
+        /// obj has to point to a live native object, e.g. the pointer of
+        /// an SGObject handle.
+        unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, name: &str) -> Result<(), ShogunError>;
+    }
+
+    /// Trait for types that can be read from an SGObject parameter
+    pub trait SGObjectGet: Sized {
+        /// Name of the type used in type mismatch errors
+        fn type_name() -> &'static str;
+        /// Takes the value out of the visitor if it holds this type,
+        /// the visitor still has to be destroyed by the caller
+        ///
+        /// # Safety
+        ///
+        /// c_visitor has to point to a live visitor returned by the C API.
+        unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self>;
+        /// Whether the type is a handle to a native object
+        #[doc(hidden)]
+        const IS_SGOBJECT: bool = false;
     }
 
-    pub fn handle_sgobject_result<T>(result: &shogun_sys::sgobject_result) -> Result<T::DerivedObject, String>
+    pub fn handle_sgobject_result<T>(result: &shogun_sys::sgobject_result) -> Result<T::DerivedObject, ShogunError>
     where T: SGObjectFromPtr {
         unsafe {
-            match result {
-                shogun_sys::sgobject_result { return_code: shogun_sys::RETURN_CODE_SUCCESS,
-                                result: shogun_sys::sgobject_result_ResultUnion { result: ptr } } => {
-                                    Ok(T::from_ptr(*ptr))
-                                },
-                shogun_sys::sgobject_result { return_code: shogun_sys::RETURN_CODE_ERROR,
-                    result: shogun_sys::sgobject_result_ResultUnion { error: msg } } => {
-                    let c_error_str = CStr::from_ptr(*msg);
-                    Err(format!("{}", c_error_str.to_str().expect("Failed to get error")))
-                },
-                _ => Err(format!("Unexpected return."))
+            match result.return_code {
+                shogun_sys::RETURN_CODE_SUCCESS => Ok(T::from_ptr(result.result.result)),
+                shogun_sys::RETURN_CODE_ERROR => Err(details::error_from_c(result.error_code, result.result.error)),
+                _ => Err(ShogunError::NativeException("Unexpected return.".to_string()))
             }
         }
     }
@@ -90,9 +480,9 @@ pub mod shogun {
     macro_rules! add_sgobject_put_type {
         ($put_type:ty, $enum_value:expr) => {
             impl SGObjectPut for $put_type {
-                fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &'static str) -> Result<(), String> {
+                unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
                     unsafe {
-                        let c_string = CString::new(parameter_name).expect("CString::new failed");
+                        let c_string = details::name_to_cstring(parameter_name)?;
                         let type_erased_parameter = std::mem::transmute::<&$put_type, *const std::ffi::c_void>(&self);
                         details::handle_result(&shogun_sys::sgobject_put(obj, c_string.as_ptr(), type_erased_parameter, $enum_value))
                     }
@@ -105,6 +495,114 @@ pub mod shogun {
     add_sgobject_put_type!(i64, shogun_sys::TYPE_INT64);
     add_sgobject_put_type!(f32, shogun_sys::TYPE_FLOAT32);
     add_sgobject_put_type!(f64, shogun_sys::TYPE_FLOAT64);
+    add_sgobject_put_type!(bool, shogun_sys::TYPE_BOOL);
+    add_sgobject_put_type!(u32, shogun_sys::TYPE_UINT32);
+    add_sgobject_put_type!(u64, shogun_sys::TYPE_UINT64);
+
+    /// Shogun chars are a single byte, so only ASCII characters can be put
+    impl SGObjectPut for char {
+        unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+            if !self.is_ascii() {
+                return Err(ShogunError::InvalidInput(format!("Cannot put non ASCII character {} in parameter {}", self, parameter_name)));
+            }
+            unsafe {
+                let c_string = details::name_to_cstring(parameter_name)?;
+                let c_char = *self as u8 as std::os::raw::c_char;
+                let type_erased_parameter = &c_char as *const std::os::raw::c_char as *const std::ffi::c_void;
+                details::handle_result(&shogun_sys::sgobject_put(obj, c_string.as_ptr(), type_erased_parameter, shogun_sys::TYPE_CHAR))
+            }
+        }
+    }
+
+    /// Sets string parameters, and enum parameters by the name of their option
+    impl SGObjectPut for str {
+        unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+            unsafe {
+                let c_string = details::name_to_cstring(parameter_name)?;
+                let c_value = CString::new(self).map_err(|_| ShogunError::InvalidInput(format!("String for parameter {} contains a null byte", parameter_name)))?;
+                let type_erased_parameter = c_value.as_ptr() as *const std::ffi::c_void;
+                details::handle_result(&shogun_sys::sgobject_put(obj, c_string.as_ptr(), type_erased_parameter, shogun_sys::TYPE_STRING))
+            }
+        }
+    }
+
+    impl<T> SGObjectPut for &T
+    where T: SGObjectPut + ?Sized {
+        unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+            (**self).sgobject_put(obj, parameter_name)
+        }
+    }
+
+    impl SGObjectPut for String {
+        unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+            self.as_str().sgobject_put(obj, parameter_name)
+        }
+    }
+
+    macro_rules! add_sgobject_get_type {
+        ($get_type:ty, $enum_value:expr) => {
+            impl SGObjectGet for $get_type {
+                fn type_name() -> &'static str {
+                    stringify!($get_type)
+                }
+                unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self> {
+                    unsafe {
+                        if shogun_sys::get_cvisitor_type(c_visitor) != $enum_value
+                            || shogun_sys::get_cvisitor_container(c_visitor) != shogun_sys::CONTAINER_TYPE_CT_SCALAR {
+                            return None;
+                        }
+                        Some(*(shogun_sys::get_cvisitor_pointer(c_visitor) as *const $get_type))
+                    }
+                }
+            }
+        }
+    }
+
+    add_sgobject_get_type!(i32, shogun_sys::TYPE_INT32);
+    add_sgobject_get_type!(i64, shogun_sys::TYPE_INT64);
+    add_sgobject_get_type!(f32, shogun_sys::TYPE_FLOAT32);
+    add_sgobject_get_type!(f64, shogun_sys::TYPE_FLOAT64);
+    add_sgobject_get_type!(bool, shogun_sys::TYPE_BOOL);
+    add_sgobject_get_type!(u32, shogun_sys::TYPE_UINT32);
+    add_sgobject_get_type!(u64, shogun_sys::TYPE_UINT64);
+
+    impl SGObjectGet for char {
+        fn type_name() -> &'static str {
+            "char"
+        }
+        unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self> {
+            unsafe {
+                if shogun_sys::get_cvisitor_type(c_visitor) != shogun_sys::TYPE_CHAR
+                    || shogun_sys::get_cvisitor_container(c_visitor) != shogun_sys::CONTAINER_TYPE_CT_SCALAR {
+                    return None;
+                }
+                Some(*(shogun_sys::get_cvisitor_pointer(c_visitor) as *const std::os::raw::c_char) as u8 as char)
+            }
+        }
+    }
+
+    /// Reads string parameters, and enum parameters as the name of their option
+    impl SGObjectGet for String {
+        fn type_name() -> &'static str {
+            "String"
+        }
+        unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self> {
+            unsafe {
+                let c_type = shogun_sys::get_cvisitor_type(c_visitor);
+                if (c_type != shogun_sys::TYPE_STRING && c_type != shogun_sys::TYPE_ENUM)
+                    || shogun_sys::get_cvisitor_container(c_visitor) != shogun_sys::CONTAINER_TYPE_CT_SCALAR {
+                    return None;
+                }
+                let c_str = CStr::from_ptr(shogun_sys::get_cvisitor_pointer(c_visitor) as *const std::os::raw::c_char);
+                Some(c_str.to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    any_sgobject!{}
+
+    // typed handles of the classes in shogun-procedural-macros/src/wrappers.manifest
+    typed_wrappers!{}
 
     pub struct Version {
         version_ptr: *mut shogun_sys::version_t,
@@ -150,45 +648,181 @@ pub mod shogun {
         ptr: *mut shogun_sys::sgobject,
     }
     pub trait MatrixToFeatures {
-        fn create_features_from_matrix(&self) -> Result<Features, String>;
+        fn create_features_from_matrix(&self) -> Result<Features, ShogunError>;
+    }
+
+    /// Matrices that features can use without copying, see
+    /// Features::from_view_mut
+    pub trait MatrixToBorrowedFeatures {
+        /// Creates features that point to the matrix memory if it is
+        /// Fortran contiguous, and that copy it otherwise
+        fn create_borrowed_features_from_matrix(&mut self) -> Result<Features, ShogunError>;
+    }
+
+    /// How samples are stored in a matrix used to create Features.
+    /// Shogun stores one sample per column, whereas most Rust code
+    /// (and CSV files) store one sample per row.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum SampleLayout {
+        /// The matrix has shape (num_samples, num_features)
+        SamplesAsRows,
+        /// The matrix has shape (num_features, num_samples), as in Shogun
+        SamplesAsColumns,
+    }
+
+    /// How Features store their samples
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum FeatureClass {
+        Dense,
+        Sparse,
+        String,
+        Combined,
+        /// Any other Shogun feature class
+        Other,
+    }
+
+    /// Element type of Features
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum FeatureType {
+        Int32,
+        Int64,
+        Float32,
+        Float64,
+        Bool,
+        UInt32,
+        UInt64,
+        Char,
+    }
+
+    impl FeatureType {
+        fn from_c(c_type: shogun_sys::TYPE) -> Option<Self> {
+            match c_type {
+                shogun_sys::TYPE_INT32 => Some(FeatureType::Int32),
+                shogun_sys::TYPE_INT64 => Some(FeatureType::Int64),
+                shogun_sys::TYPE_FLOAT32 => Some(FeatureType::Float32),
+                shogun_sys::TYPE_FLOAT64 => Some(FeatureType::Float64),
+                shogun_sys::TYPE_BOOL => Some(FeatureType::Bool),
+                shogun_sys::TYPE_UINT32 => Some(FeatureType::UInt32),
+                shogun_sys::TYPE_UINT64 => Some(FeatureType::UInt64),
+                shogun_sys::TYPE_CHAR => Some(FeatureType::Char),
+                _ => None,
+            }
+        }
+    }
+
+    /// Alphabets of string features, Shogun rejects strings with symbols
+    /// outside of the alphabet
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Alphabet {
+        /// A, C, G and T, in either case
+        Dna,
+        /// DNA stored as the numbers 0 to 3
+        RawDna,
+        /// A, C, G and U, in either case
+        Rna,
+        Protein,
+        Binary,
+        Alphanum,
+        Cube,
+        /// Any byte, e.g. for plain text
+        RawByte,
+        IupacNucleicAcid,
+        IupacAminoAcid,
+        Digit,
+        Digit2,
+        RawDigit,
+        RawDigit2,
+        Snp,
+        RawSnp,
+    }
+
+    impl Alphabet {
+        fn to_c(self) -> shogun_sys::ALPHABET {
+            match self {
+                Alphabet::Dna => shogun_sys::ALPHABET_AL_DNA,
+                Alphabet::RawDna => shogun_sys::ALPHABET_AL_RAWDNA,
+                Alphabet::Rna => shogun_sys::ALPHABET_AL_RNA,
+                Alphabet::Protein => shogun_sys::ALPHABET_AL_PROTEIN,
+                Alphabet::Binary => shogun_sys::ALPHABET_AL_BINARY,
+                Alphabet::Alphanum => shogun_sys::ALPHABET_AL_ALPHANUM,
+                Alphabet::Cube => shogun_sys::ALPHABET_AL_CUBE,
+                Alphabet::RawByte => shogun_sys::ALPHABET_AL_RAWBYTE,
+                Alphabet::IupacNucleicAcid => shogun_sys::ALPHABET_AL_IUPAC_NUCLEIC_ACID,
+                Alphabet::IupacAminoAcid => shogun_sys::ALPHABET_AL_IUPAC_AMINO_ACID,
+                Alphabet::Digit => shogun_sys::ALPHABET_AL_DIGIT,
+                Alphabet::Digit2 => shogun_sys::ALPHABET_AL_DIGIT2,
+                Alphabet::RawDigit => shogun_sys::ALPHABET_AL_RAWDIGIT,
+                Alphabet::RawDigit2 => shogun_sys::ALPHABET_AL_RAWDIGIT2,
+                Alphabet::Snp => shogun_sys::ALPHABET_AL_SNP,
+                Alphabet::RawSnp => shogun_sys::ALPHABET_AL_RAWSNP,
+            }
+        }
     }
 
     macro_rules! add_matrix_type {
         ($array_type:ty, $enum_value:expr) => {
-            impl MatrixToFeatures for Array2<$array_type> {
-                fn create_features_from_matrix(&self) -> Result<Features, String> {
-                    let n_rows = self.nrows();
-                    let n_cols = self.ncols();
+            impl MatrixToFeatures for ArrayView2<'_, $array_type> {
+                fn create_features_from_matrix(&self) -> Result<Features, ShogunError> {
+                    let n_rows = details::to_u32(self.nrows(), "rows")?;
+                    let n_cols = details::to_u32(self.ncols(), "columns")?;
+                    let data = details::to_column_major(self.view());
                     unsafe {
-                        let data = self.as_ptr();
-                        let type_erased_matrix = std::mem::transmute::<*const $array_type, *const std::ffi::c_void>(data);
-                        match shogun_sys::create_features_from_data(type_erased_matrix, n_rows as u32, n_cols as u32, $enum_value) {
-                            shogun_sys::sgobject_result { return_code: shogun_sys::RETURN_CODE_SUCCESS,
-                                result: shogun_sys::sgobject_result_ResultUnion { result: ptr } } => {
-                                  Ok(Features { ptr })
-                              },
-                            shogun_sys::sgobject_result { return_code: shogun_sys::RETURN_CODE_ERROR,
-                                result: shogun_sys::sgobject_result_ResultUnion { error: msg } } => {
-                                let c_error_str = CStr::from_ptr(msg);
-                                Err(format!("{}", c_error_str.to_str().expect("Failed to get error")))
-                            },
-                            _ => Err("Unexpected return.".to_string())
-                        }
+                        let type_erased_matrix = data.as_ptr() as *const std::ffi::c_void;
+                        let c_ptr = shogun_sys::create_features_from_data(type_erased_matrix, n_rows, n_cols, $enum_value);
+                        handle_sgobject_result::<Features>(&c_ptr)
+                    }
+                }
+            }
+            impl MatrixToBorrowedFeatures for ArrayViewMut2<'_, $array_type> {
+                fn create_borrowed_features_from_matrix(&mut self) -> Result<Features, ShogunError> {
+                    if !self.t().is_standard_layout() {
+                        return self.view().create_features_from_matrix();
+                    }
+                    let n_rows = details::to_u32(self.nrows(), "rows")?;
+                    let n_cols = details::to_u32(self.ncols(), "columns")?;
+                    unsafe {
+                        let type_erased_matrix = self.as_mut_ptr() as *mut std::ffi::c_void;
+                        let c_ptr = shogun_sys::create_features_from_borrowed_data(type_erased_matrix, n_rows, n_cols, $enum_value);
+                        handle_sgobject_result::<Features>(&c_ptr)
+                    }
+                }
+            }
+            impl SGObjectPut for ArrayView2<'_, $array_type> {
+                unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+                    let n_rows = details::to_u32(self.nrows(), "rows")?;
+                    let n_cols = details::to_u32(self.ncols(), "columns")?;
+                    let data = details::to_column_major(self.view());
+                    unsafe {
+                        let c_string = details::name_to_cstring(parameter_name)?;
+                        let type_erased_matrix = data.as_ptr() as *const std::ffi::c_void;
+                        details::handle_result(&shogun_sys::sgobject_put_array(obj, c_string.as_ptr(), type_erased_matrix, n_rows, n_cols, $enum_value, shogun_sys::CONTAINER_TYPE_CT_MATRIX))
                     }
                 }
             }
             impl SGObjectPut for Array2<$array_type> {
-                fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &'static str) -> Result<(), String> {
-                    let n_rows = self.nrows() as u32;
-                    let n_cols = self.ncols() as u32;
+                unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+                    self.view().sgobject_put(obj, parameter_name)
+                }
+            }
+            impl SGObjectGet for Array2<$array_type> {
+                fn type_name() -> &'static str {
+                    concat!("Array2<", stringify!($array_type), ">")
+                }
+                unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self> {
                     unsafe {
-                        let data = self.as_ptr();
-                        let c_string = CString::new(parameter_name).expect("CString::new failed");
-                        let type_erased_matrix = std::mem::transmute::<*const $array_type, *const std::ffi::c_void>(data);
-                        details::handle_result(&shogun_sys::sgobject_put_array(obj, c_string.as_ptr(), type_erased_matrix, n_rows, n_cols, $enum_value))
+                        if shogun_sys::get_cvisitor_type(c_visitor) != $enum_value
+                            || shogun_sys::get_cvisitor_container(c_visitor) != shogun_sys::CONTAINER_TYPE_CT_MATRIX {
+                            return None;
+                        }
+                        // SGMatrix data is column-major
+                        let data = shogun_sys::get_cvisitor_pointer(c_visitor) as *const $array_type;
+                        let n_rows = shogun_sys::get_cvisitor_rows(c_visitor) as usize;
+                        let n_cols = shogun_sys::get_cvisitor_cols(c_visitor) as usize;
+                        let values = std::slice::from_raw_parts(data, n_rows * n_cols).to_vec();
+                        Some(Array2::from_shape_vec((n_rows, n_cols).f(), values).expect("Invalid matrix shape"))
                     }
                 }
-            }       
+            }
         };
     }
 
@@ -197,53 +831,399 @@ pub mod shogun {
     add_matrix_type!(i32, shogun_sys::TYPE_INT32);
     add_matrix_type!(i64, shogun_sys::TYPE_INT64);
 
+    macro_rules! add_vector_type {
+        ($array_type:ty, $enum_value:expr) => {
+            impl SGObjectPut for Array1<$array_type> {
+                unsafe fn sgobject_put(&self, obj: *mut shogun_sys::sgobject, parameter_name: &str) -> Result<(), ShogunError> {
+                    // vectors are passed as a single column
+                    let len = details::to_u32(self.len(), "elements")?;
+                    let data = self.as_standard_layout();
+                    unsafe {
+                        let c_string = details::name_to_cstring(parameter_name)?;
+                        let type_erased_vector = data.as_ptr() as *const std::ffi::c_void;
+                        details::handle_result(&shogun_sys::sgobject_put_array(obj, c_string.as_ptr(), type_erased_vector, len, 1, $enum_value, shogun_sys::CONTAINER_TYPE_CT_VECTOR))
+                    }
+                }
+            }
+            impl SGObjectGet for Array1<$array_type> {
+                fn type_name() -> &'static str {
+                    concat!("Array1<", stringify!($array_type), ">")
+                }
+                unsafe fn from_cvisitor(c_visitor: *mut shogun_sys::cvisitor_t) -> Option<Self> {
+                    unsafe {
+                        if shogun_sys::get_cvisitor_type(c_visitor) != $enum_value
+                            || shogun_sys::get_cvisitor_container(c_visitor) != shogun_sys::CONTAINER_TYPE_CT_VECTOR {
+                            return None;
+                        }
+                        let data = shogun_sys::get_cvisitor_pointer(c_visitor) as *const $array_type;
+                        let len = shogun_sys::get_cvisitor_rows(c_visitor) as usize;
+                        Some(Array1::from(std::slice::from_raw_parts(data, len).to_vec()))
+                    }
+                }
+            }
+        };
+    }
+
+    add_vector_type!(f32, shogun_sys::TYPE_FLOAT32);
+    add_vector_type!(f64, shogun_sys::TYPE_FLOAT64);
+    add_vector_type!(i32, shogun_sys::TYPE_INT32);
+    add_vector_type!(i64, shogun_sys::TYPE_INT64);
+
     impl Features {
-        pub fn from_array<T>(array: &Array2<T>) -> Result<Features, String>
-        where Array2<T>: MatrixToFeatures {
-            array.create_features_from_matrix()
+        /// Creates dense features from a matrix with one sample per column,
+        /// which is Shogun's convention. Any memory layout is accepted,
+        /// the data is copied into column-major order.
+        pub fn from_array<'a, T: 'a, V>(array: V) -> Result<Features, ShogunError>
+        where V: AsArray<'a, T, Ix2>, ArrayView2<'a, T>: MatrixToFeatures {
+            Features::from_array_with_layout(array, SampleLayout::SamplesAsColumns)
+        }
+
+        /// Creates dense features from a matrix, with the samples stored as
+        /// described by layout
+        pub fn from_array_with_layout<'a, T: 'a, V>(array: V, layout: SampleLayout) -> Result<Features, ShogunError>
+        where V: AsArray<'a, T, Ix2>, ArrayView2<'a, T>: MatrixToFeatures {
+            let view = array.into();
+            match layout {
+                SampleLayout::SamplesAsColumns => view.create_features_from_matrix(),
+                SampleLayout::SamplesAsRows => view.reversed_axes().create_features_from_matrix(),
+            }
+        }
+
+        /// Creates dense features with one sample per column that use the
+        /// memory of the view without copying it, as long as the view is
+        /// Fortran contiguous (e.g. `Array2::zeros((n, m).f())`).
+        /// Other layouts are copied. The view is mutable because Shogun may
+        /// write to the features, e.g. when normalising them in place.
+        pub fn from_view_mut<'a, T>(mut view: ArrayViewMut2<'a, T>) -> Result<BorrowedFeatures<'a>, ShogunError>
+        where ArrayViewMut2<'a, T>: MatrixToBorrowedFeatures {
+            Ok(BorrowedFeatures {
+                features: view.create_borrowed_features_from_matrix()?,
+                data: PhantomData,
+            })
         }
 
-        pub fn from_file(file: &File) -> Result<Features, String> {
+        /// Creates string features, e.g. for WeightedDegreeStringKernel,
+        /// with one sample per string. The strings are copied.
+        pub fn from_strings<S: AsRef<[u8]>>(strings: &[S], alphabet: Alphabet) -> Result<Features, ShogunError> {
+            let num_strings = details::to_u32(strings.len(), "strings")?;
+            let mut data: Vec<u8> = Vec::new();
+            let mut offsets: Vec<u64> = vec![0];
+            for string in strings {
+                data.extend_from_slice(string.as_ref());
+                offsets.push(data.len() as u64);
+            }
+            unsafe {
+                let c_ptr = shogun_sys::create_string_features(
+                    data.as_ptr() as *const std::os::raw::c_char, offsets.as_ptr(),
+                    num_strings, alphabet.to_c());
+                handle_sgobject_result::<Self>(&c_ptr)
+            }
+        }
+
+        pub fn from_file(file: &File) -> Result<Features, ShogunError> {
             unsafe {
                 let c_ptr = shogun_sys::create_features_from_file(file.ptr);
                 handle_sgobject_result::<Self>(&c_ptr)
             }
         }
+
+        /// Restricts the features to the samples at indices, without
+        /// copying them, until the returned guard is dropped
+        pub fn with_subset(&mut self, indices: &[usize]) -> Result<Subset<'_, Features>, ShogunError> {
+            Subset::new(self, indices)
+        }
+
+        /// Number of samples
+        pub fn num_vectors(&self) -> Result<usize, ShogunError> {
+            details::handle_uint64_result(unsafe { &shogun_sys::get_features_num_vectors(self.ptr) })
+                .map(|num_vectors| num_vectors as usize)
+        }
+
+        /// Dimension of the samples, for string features the length of the
+        /// longest string. Fails with TypeMismatch for feature classes
+        /// without a dimension, such as combined features.
+        pub fn num_features(&self) -> Result<usize, ShogunError> {
+            details::handle_uint64_result(unsafe { &shogun_sys::get_features_num_features(self.ptr) })
+                .map(|num_features| num_features as usize)
+        }
+
+        pub fn feature_class(&self) -> FeatureClass {
+            match unsafe { shogun_sys::get_features_class(self.ptr) } {
+                shogun_sys::FEATURE_CLASS_FC_DENSE => FeatureClass::Dense,
+                shogun_sys::FEATURE_CLASS_FC_SPARSE => FeatureClass::Sparse,
+                shogun_sys::FEATURE_CLASS_FC_STRING => FeatureClass::String,
+                shogun_sys::FEATURE_CLASS_FC_COMBINED => FeatureClass::Combined,
+                _ => FeatureClass::Other,
+            }
+        }
+
+        /// Fails with TypeMismatch if the features have no element type,
+        /// e.g. combined features, or one without a FeatureType
+        pub fn feature_type(&self) -> Result<FeatureType, ShogunError> {
+            let c_type = details::handle_type_result(unsafe { &shogun_sys::get_features_type(self.ptr) })?;
+            FeatureType::from_c(c_type)
+                .ok_or_else(|| ShogunError::TypeMismatch("Unexpected feature type".to_string()))
+        }
+    }
+
+    /// Collects the parameters of a new object, which are only put when
+    /// the object is built, see the builder function of the SGObject types
+    pub struct SGObjectBuilder<'a, T> {
+        class_name: String,
+        parameters: Vec<(String, Box<dyn SGObjectPut + 'a>)>,
+        object_type: PhantomData<T>,
+    }
+
+    impl<'a, T> SGObjectBuilder<'a, T>
+    where T: SGObject<DerivedObject = T> {
+        pub fn new(class_name: &str) -> Self {
+            SGObjectBuilder {
+                class_name: class_name.to_string(),
+                parameters: Vec::new(),
+                object_type: PhantomData,
+            }
+        }
+
+        /// Adds a parameter, which is checked when the object is built
+        pub fn set<V>(mut self, parameter_name: &str, parameter_value: V) -> Self
+        where V: SGObjectPut + 'a {
+            self.parameters.push((parameter_name.to_string(), Box::new(parameter_value)));
+            self
+        }
+
+        /// Creates the object and puts all the parameters, every failed put
+        /// is reported instead of only the first one
+        pub fn build(self) -> Result<T, ShogunError> {
+            let mut obj = T::create(&self.class_name)?;
+            let mut errors: Vec<ShogunError> = self.parameters.iter()
+                .filter_map(|(name, value)| obj.put(name, value.as_ref()).err())
+                .collect();
+            match errors.len() {
+                0 => Ok(obj),
+                1 => Err(errors.remove(0)),
+                _ => Err(ShogunError::Multiple(errors)),
+            }
+        }
+    }
+
+    /// Features that may point to memory owned by an ndarray, see
+    /// Features::from_view_mut. They cannot be put into other objects, a
+    /// machine can only be trained on them with Machine::train_borrowed,
+    /// which ties the trained machine to the lifetime of the features.
+    pub struct BorrowedFeatures<'a> {
+        features: Features,
+        data: PhantomData<&'a mut ()>,
+    }
+
+    impl BorrowedFeatures<'_> {
+        pub fn num_vectors(&self) -> Result<usize, ShogunError> {
+            self.features.num_vectors()
+        }
+
+        pub fn num_features(&self) -> Result<usize, ShogunError> {
+            self.features.num_features()
+        }
+
+        /// Copies the features and their data, the copy can be used
+        /// like any other Features
+        pub fn deep_clone(&self) -> Result<Features, ShogunError> {
+            self.features.deep_clone()
+        }
+    }
+
+    impl fmt::Display for BorrowedFeatures<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.features)
+        }
+    }
+
+    /// A machine trained on BorrowedFeatures, see Machine::train_borrowed.
+    /// The machine and the objects it reaches may point to the borrowed
+    /// data, so it cannot outlive the features and never hands out handles
+    /// to them. `detach` or `deep_clone` give an independent Machine.
+    pub struct BorrowingMachine<'a> {
+        machine: Machine,
+        features: PhantomData<&'a ()>,
+    }
+
+    impl BorrowingMachine<'_> {
+        pub fn apply(&self, features: &Features) -> Result<Labels, ShogunError> {
+            self.machine.apply(features)
+        }
+
+        pub fn apply_multiclass(&self, features: &Features) -> Result<Labels, ShogunError> {
+            self.machine.apply_multiclass(features)
+        }
+
+        /// Reads a parameter of the machine. Object parameters, such as the
+        /// kernel, may point to the borrowed data and are only available
+        /// from a detached machine.
+        pub fn get<T: SGObjectGet>(&self, parameter_name: &str) -> Result<T, ShogunError> {
+            if T::IS_SGOBJECT {
+                return Err(ShogunError::InvalidInput(format!(
+                    "Cannot read object parameter {} of a machine trained on borrowed features, detach it first", parameter_name)));
+            }
+            self.machine.get(parameter_name)
+        }
+
+        pub fn class_name(&self) -> String {
+            self.machine.class_name()
+        }
+
+        pub fn to_json(&self) -> Result<String, ShogunError> {
+            self.machine.to_json()
+        }
+
+        pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ShogunError> {
+            self.machine.save(path)
+        }
+
+        pub fn save_as<P: AsRef<Path>>(&self, path: P, format: SerializationFormat) -> Result<(), ShogunError> {
+            self.machine.save_as(path, format)
+        }
+
+        /// Independent copy of the trained machine, Shogun's clone copies
+        /// the data of all parameters, so the copy can outlive the features
+        pub fn deep_clone(&self) -> Result<Machine, ShogunError> {
+            self.machine.deep_clone()
+        }
+
+        /// Turns the machine into an independent Machine, see deep_clone
+        pub fn detach(self) -> Result<Machine, ShogunError> {
+            self.deep_clone()
+        }
+    }
+
+    impl fmt::Display for BorrowingMachine<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.machine)
+        }
+    }
+
+    /// Features or Labels restricted to a subset of their samples, see
+    /// Features::with_subset and Labels::with_subset. The samples are not
+    /// copied, and the subset is removed when the guard is dropped. Handles
+    /// created with share see the subset too.
+    pub struct Subset<'a, T: HasSGObjectPtr> {
+        obj: &'a mut T,
+    }
+
+    impl<'a, T: HasSGObjectPtr> Subset<'a, T> {
+        fn new(obj: &'a mut T, indices: &[usize]) -> Result<Self, ShogunError> {
+            let c_indices: Vec<u64> = indices.iter().map(|&idx| idx as u64).collect();
+            details::handle_result(unsafe {
+                &shogun_sys::add_subset(obj.get_ptr(), c_indices.as_ptr(), c_indices.len() as u64)
+            })?;
+            Ok(Subset { obj })
+        }
+
+        /// Restricts the subset further, the indices refer to the samples
+        /// of this subset
+        pub fn with_subset(&mut self, indices: &[usize]) -> Result<Subset<'_, T>, ShogunError> {
+            Subset::new(&mut *self.obj, indices)
+        }
+    }
+
+    impl<T: HasSGObjectPtr> Deref for Subset<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            self.obj
+        }
+    }
+
+    impl<T: HasSGObjectPtr + fmt::Display> fmt::Display for Subset<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.obj)
+        }
+    }
+
+    impl<T: HasSGObjectPtr> Drop for Subset<'_, T> {
+        fn drop(&mut self) {
+            // errors cannot be reported from drop, handle_result still
+            // releases the error message
+            let result = unsafe { shogun_sys::remove_subset(self.obj.get_ptr()) };
+            let _ = details::handle_result(&result);
+        }
     }
 
     impl Kernel {
-        pub fn init(&mut self, lhs: &Features, rhs: &Features) -> Result<(), String> {
+        pub fn init(&mut self, lhs: &Features, rhs: &Features) -> Result<(), ShogunError> {
             unsafe {
                 details::handle_result(&shogun_sys::init_kernel(self.ptr, lhs.ptr, rhs.ptr))
             }
         }
     }
 
+    impl CombinedFeatures {
+        /// Appends features as the next part of each sample, all parts
+        /// need the same number of vectors
+        pub fn append(self, features: &Features) -> Result<Self, ShogunError> {
+            details::handle_result(unsafe { &shogun_sys::combined_features_append(self.0.ptr, features.ptr) })?;
+            Ok(self)
+        }
+    }
+
+    impl CombinedKernel {
+        /// Appends a kernel for the next part of CombinedFeatures
+        pub fn append(self, kernel: &Kernel) -> Result<Self, ShogunError> {
+            details::handle_result(unsafe { &shogun_sys::combined_kernel_append(self.0.ptr, kernel.ptr) })?;
+            Ok(self)
+        }
+
+        /// Weight of each subkernel, e.g. as learned by an MKL machine
+        pub fn subkernel_weights(&self) -> Result<Array1<f64>, ShogunError> {
+            unsafe {
+                let c_visitor = details::handle_cvisitor_result(&shogun_sys::get_subkernel_weights(self.0.ptr))?;
+                let weights = Array1::<f64>::from_cvisitor(c_visitor);
+                shogun_sys::destroy_cvisitor(c_visitor);
+                weights.ok_or_else(|| ShogunError::TypeMismatch("Expected subkernel weights of type f64".to_string()))
+            }
+        }
+    }
+
     impl Machine {
-        pub fn train(&mut self, features: &Features) -> Result<(), String> {
+        pub fn train(&mut self, features: &Features) -> Result<(), ShogunError> {
             unsafe {
                 details::handle_result(&shogun_sys::train_machine(self.ptr, features.ptr))
             }
         }
-        pub fn apply(&self, features: &Features) -> Result<Labels, String> {
+        pub fn apply(&self, features: &Features) -> Result<Labels, ShogunError> {
             unsafe {
                 let c_ptr = shogun_sys::apply_machine(self.ptr, features.ptr);
                 handle_sgobject_result::<Labels>(&c_ptr)
             }
         }
 
-        pub fn apply_multiclass(&self, features: &Features) -> Result<Labels, String> {
+        pub fn apply_multiclass(&self, features: &Features) -> Result<Labels, ShogunError> {
             unsafe {
                 let c_ptr = shogun_sys::apply_multiclass_machine(self.ptr, features.ptr);
                 handle_sgobject_result::<Labels>(&c_ptr)
             }
         }
+
+        /// Trains a copy of the machine on borrowed features. Machines and
+        /// the objects they reach, e.g. their kernel, may keep pointing to
+        /// the training data, so the copy is private to a BorrowingMachine
+        /// that cannot outlive the features. Only the untrained machine is
+        /// copied, and this machine is left unchanged.
+        pub fn train_borrowed<'a>(&self, features: &'a BorrowedFeatures<'_>) -> Result<BorrowingMachine<'a>, ShogunError> {
+            let mut machine = self.deep_clone()?;
+            machine.train(&features.features)?;
+            Ok(BorrowingMachine { machine, features: PhantomData })
+        }
+
+        /// Applies a copy of the machine to borrowed features, since
+        /// machines may keep pointing to the features they were applied to
+        pub fn apply_borrowed(&self, features: &BorrowedFeatures<'_>) -> Result<Labels, ShogunError> {
+            self.deep_clone()?.apply(&features.features)
+        }
     }
 
     impl File {
-        pub fn read_csv(filepath: String) -> Result<Self, String> {
+        pub fn read_csv(filepath: String) -> Result<Self, ShogunError> {
             unsafe {
-                let c_string = CString::new(filepath).expect("CString::new failed");
+                let c_string = details::name_to_cstring(&filepath)?;
                 let c_ptr = shogun_sys::read_csvfile(c_string.as_ptr());
                 handle_sgobject_result::<Self>(&c_ptr)
             }
@@ -251,29 +1231,28 @@ pub mod shogun {
     }
 
     impl Labels {
-        pub fn from_file(file: &File) -> Result<Labels, String> {
+        pub fn from_file(file: &File) -> Result<Labels, ShogunError> {
             unsafe {
                 let c_ptr = shogun_sys::create_labels_from_file(file.ptr);
                 handle_sgobject_result::<Labels>(&c_ptr)
             }
         }
+
+        /// Restricts the labels to the samples at indices, without copying
+        /// them, until the returned guard is dropped
+        pub fn with_subset(&mut self, indices: &[usize]) -> Result<Subset<'_, Labels>, ShogunError> {
+            Subset::new(self, indices)
+        }
     }
 
     impl Evaluation {
-        pub fn evaluate(&self, y_pred: &Labels, y_true: &Labels) -> Result<f64, String> {
+        pub fn evaluate(&self, y_pred: &Labels, y_true: &Labels) -> Result<f64, ShogunError> {
             unsafe {
                 let c_ptr = shogun_sys::evaluate_labels(self.ptr, y_pred.ptr, y_true.ptr);
-                match c_ptr {
-                    shogun_sys::float64_result { return_code: shogun_sys::RETURN_CODE_SUCCESS,
-                                    result: shogun_sys::float64_result_ResultFloat64Union { result: value } } => {
-                                        Ok( value )
-                                    },
-                    shogun_sys::float64_result { return_code: shogun_sys::RETURN_CODE_ERROR,
-                        result: shogun_sys::float64_result_ResultFloat64Union { error: msg } } => {
-                        let c_error_str = CStr::from_ptr(msg);
-                        Err(format!("{}", c_error_str.to_str().expect("Failed to get error")))
-                    },
-                    _ => Err(format!("Unexpected return."))
+                match c_ptr.return_code {
+                    shogun_sys::RETURN_CODE_SUCCESS => Ok(c_ptr.result.result),
+                    shogun_sys::RETURN_CODE_ERROR => Err(details::error_from_c(c_ptr.error_code, c_ptr.result.error)),
+                    _ => Err(ShogunError::NativeException("Unexpected return.".to_string()))
                 }
             }
         }
@@ -286,19 +1265,31 @@ pub mod shogun {
             }
         }
 
-        pub fn main_version(&self) -> Result<String, String> {
+        pub fn main_version(&self) -> Result<String, ShogunError> {
             let char_ptr = unsafe { shogun_sys::get_version_main(self.version_ptr) };
+            if char_ptr.is_null() {
+                return Err(ShogunError::NativeException("Failed to get the Shogun version".to_string()));
+            }
             let c_str = unsafe { CStr::from_ptr(char_ptr) };
             match c_str.to_str() {
-                Err(x) => Err(x.to_string()),
+                Err(x) => Err(ShogunError::NativeException(x.to_string())),
                 Ok(x) => Ok(x.to_string()),
             }
         }
     }
 
 
-    pub fn set_num_threads(n_threads: i32) {
-        unsafe {shogun_sys::set_parallel_threads(n_threads)};
+    pub fn set_num_threads(n_threads: i32) -> Result<(), ShogunError> {
+        details::handle_result(unsafe { &shogun_sys::set_parallel_threads(n_threads) })
+    }
+
+    /// Number of objects and strings handed out by the C API that have not
+    /// been released yet, used by the tests to check that the bindings
+    /// release what they get from the C API. Allocations made by Shogun
+    /// itself are not counted, so this is not a general leak checker.
+    #[doc(hidden)]
+    pub fn live_allocations() -> i64 {
+        unsafe { shogun_sys::get_live_allocations() }
     }
 
     impl Drop for Version {
@@ -306,4 +1297,281 @@ pub mod shogun {
             unsafe { shogun_sys::destroy_version(self.version_ptr) };
         }
     }
+
+    #[cfg(feature = "serde")]
+    pub use snapshot::{ParameterSnapshot, ParameterValue};
+
+    #[cfg(feature = "serde")]
+    mod snapshot {
+        use super::{details, AnySGObject, SGObject, ShogunError};
+        use ndarray::{Array1, Array2};
+        use serde::{Deserialize, Serialize};
+        use std::any::Any;
+        use std::collections::BTreeMap;
+
+        /// Class and parameters of an SGObject, which can be stored with
+        /// serde and turned back into an object with create and put
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        pub struct ParameterSnapshot {
+            /// Name of the wrapper type, e.g. Kernel
+            pub base_type: String,
+            /// Name of the Shogun class, e.g. GaussianKernel
+            pub class_name: String,
+            pub parameters: BTreeMap<String, ParameterValue>,
+        }
+
+        /// Parameter value of a snapshot, matrices are stored row by row
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        pub enum ParameterValue {
+            Int32(i32),
+            Int64(i64),
+            UInt32(u32),
+            UInt64(u64),
+            Float32(f32),
+            Float64(f64),
+            Bool(bool),
+            Char(char),
+            /// Strings and the option names of enums
+            String(String),
+            Int32Vector(Vec<i32>),
+            Int64Vector(Vec<i64>),
+            Float32Vector(Vec<f32>),
+            Float64Vector(Vec<f64>),
+            Int32Matrix(Vec<Vec<i32>>),
+            Int64Matrix(Vec<Vec<i64>>),
+            Float32Matrix(Vec<Vec<f32>>),
+            Float64Matrix(Vec<Vec<f64>>),
+            Object(ParameterSnapshot),
+        }
+
+        fn matrix_rows<T: Clone>(matrix: &Array2<T>) -> Vec<Vec<T>> {
+            matrix.outer_iter().map(|row| row.to_vec()).collect()
+        }
+
+        fn matrix_from_rows<T: Clone>(rows: &[Vec<T>]) -> Result<Array2<T>, ShogunError> {
+            let cols = rows.first().map_or(0, |row| row.len());
+            let data = rows.iter().flatten().cloned().collect();
+            Array2::from_shape_vec((rows.len(), cols), data)
+                .map_err(|_| ShogunError::InvalidInput("Matrix rows have different lengths".to_string()))
+        }
+
+        macro_rules! snapshot_value {
+            ($value:expr, $( $value_type:ty => $variant:expr ),* ) => {{
+                let value: &Box<dyn Any> = $value;
+                if let Some(snapshot) = AnySGObject::snapshot_of_any(value.as_ref()) {
+                    return Ok(Some(ParameterValue::Object(snapshot?)));
+                }
+                $(
+                    if let Some(value) = value.downcast_ref::<$value_type>() {
+                        return Ok(Some($variant(value)));
+                    }
+                )*
+                Ok(None)
+            }}
+        }
+
+        impl ParameterValue {
+            /// None if the value has a type that snapshots cannot store
+            fn from_any(value: &Box<dyn Any>) -> Result<Option<Self>, ShogunError> {
+                snapshot_value!(value,
+                    i32 => |v: &i32| ParameterValue::Int32(*v),
+                    i64 => |v: &i64| ParameterValue::Int64(*v),
+                    u32 => |v: &u32| ParameterValue::UInt32(*v),
+                    u64 => |v: &u64| ParameterValue::UInt64(*v),
+                    f32 => |v: &f32| ParameterValue::Float32(*v),
+                    f64 => |v: &f64| ParameterValue::Float64(*v),
+                    bool => |v: &bool| ParameterValue::Bool(*v),
+                    char => |v: &char| ParameterValue::Char(*v),
+                    String => |v: &String| ParameterValue::String(v.clone()),
+                    Array1<i32> => |v: &Array1<i32>| ParameterValue::Int32Vector(v.to_vec()),
+                    Array1<i64> => |v: &Array1<i64>| ParameterValue::Int64Vector(v.to_vec()),
+                    Array1<f32> => |v: &Array1<f32>| ParameterValue::Float32Vector(v.to_vec()),
+                    Array1<f64> => |v: &Array1<f64>| ParameterValue::Float64Vector(v.to_vec()),
+                    Array2<i32> => |v: &Array2<i32>| ParameterValue::Int32Matrix(matrix_rows(v)),
+                    Array2<i64> => |v: &Array2<i64>| ParameterValue::Int64Matrix(matrix_rows(v)),
+                    Array2<f32> => |v: &Array2<f32>| ParameterValue::Float32Matrix(matrix_rows(v)),
+                    Array2<f64> => |v: &Array2<f64>| ParameterValue::Float64Matrix(matrix_rows(v))
+                )
+            }
+
+            fn put_into<T: SGObject>(&self, obj: &mut T, name: &str) -> Result<(), ShogunError> {
+                match self {
+                    ParameterValue::Int32(v) => obj.put(name, v),
+                    ParameterValue::Int64(v) => obj.put(name, v),
+                    ParameterValue::UInt32(v) => obj.put(name, v),
+                    ParameterValue::UInt64(v) => obj.put(name, v),
+                    ParameterValue::Float32(v) => obj.put(name, v),
+                    ParameterValue::Float64(v) => obj.put(name, v),
+                    ParameterValue::Bool(v) => obj.put(name, v),
+                    ParameterValue::Char(v) => obj.put(name, v),
+                    ParameterValue::String(v) => obj.put(name, v),
+                    ParameterValue::Int32Vector(v) => obj.put(name, &Array1::from(v.clone())),
+                    ParameterValue::Int64Vector(v) => obj.put(name, &Array1::from(v.clone())),
+                    ParameterValue::Float32Vector(v) => obj.put(name, &Array1::from(v.clone())),
+                    ParameterValue::Float64Vector(v) => obj.put(name, &Array1::from(v.clone())),
+                    ParameterValue::Int32Matrix(v) => obj.put(name, &matrix_from_rows(v)?),
+                    ParameterValue::Int64Matrix(v) => obj.put(name, &matrix_from_rows(v)?),
+                    ParameterValue::Float32Matrix(v) => obj.put(name, &matrix_from_rows(v)?),
+                    ParameterValue::Float64Matrix(v) => obj.put(name, &matrix_from_rows(v)?),
+                    ParameterValue::Object(snapshot) => obj.put(name, &snapshot.restore_any()?),
+                }
+            }
+        }
+
+        impl ParameterSnapshot {
+            /// Takes the class and every settable parameter whose value can be
+            /// stored, nested objects are stored as snapshots. Model
+            /// parameters, read-only parameters and unset objects are skipped,
+            /// parameters that cannot be read are reported.
+            pub fn new<T: SGObject>(obj: &T) -> Result<Self, ShogunError> {
+                let mut parameters = BTreeMap::new();
+                for parameter in obj.parameters()?.into_iter().filter(|parameter| parameter.settable()) {
+                    let value = match parameter.value() {
+                        Ok(value) => value,
+                        Err(error) if error == details::unset_parameter_error(&parameter.name) => continue,
+                        Err(error) => return Err(error),
+                    };
+                    if let Some(value) = ParameterValue::from_any(&value)? {
+                        parameters.insert(parameter.name, value);
+                    }
+                }
+                Ok(ParameterSnapshot {
+                    base_type: AnySGObject::base_type_of(obj).to_string(),
+                    class_name: obj.class_name(),
+                    parameters,
+                })
+            }
+
+            /// Creates an object of the snapshot's class and puts all its
+            /// parameters, every failed put is reported
+            pub fn restore<T>(&self) -> Result<T, ShogunError>
+            where T: SGObject<DerivedObject = T> {
+                let mut obj = T::create(&self.class_name)?;
+                self.put_parameters(&mut obj)?;
+                Ok(obj)
+            }
+
+            fn restore_any(&self) -> Result<AnySGObject, ShogunError> {
+                AnySGObject::restore(self)
+            }
+
+            /// Puts the parameters of the snapshot that are settable in obj,
+            /// unknown names are reported by put
+            pub(super) fn put_parameters<T: SGObject>(&self, obj: &mut T) -> Result<(), ShogunError> {
+                let not_settable: Vec<String> = obj.parameters()?.into_iter()
+                    .filter(|parameter| !parameter.settable())
+                    .map(|parameter| parameter.name)
+                    .collect();
+                let mut errors: Vec<ShogunError> = self.parameters.iter()
+                    .filter(|(name, _)| !not_settable.contains(name))
+                    .filter_map(|(name, value)| value.put_into(obj, name).err())
+                    .collect();
+                match errors.len() {
+                    0 => Ok(()),
+                    1 => Err(errors.remove(0)),
+                    _ => Err(ShogunError::Multiple(errors)),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "sprs")]
+    pub use sparse::SparseElement;
+
+    #[cfg(feature = "sprs")]
+    mod sparse {
+        use super::{details, handle_sgobject_result, Features, SampleLayout, ShogunError};
+        use sprs::{CompressedStorage, CsMat};
+        use std::convert::TryFrom;
+
+        /// Element types that can be stored in sparse features
+        pub trait SparseElement: Copy + Default {
+            #[doc(hidden)]
+            fn c_type() -> shogun_sys::TYPE;
+        }
+
+        impl SparseElement for f32 {
+            fn c_type() -> shogun_sys::TYPE {
+                shogun_sys::TYPE_FLOAT32
+            }
+        }
+
+        impl SparseElement for f64 {
+            fn c_type() -> shogun_sys::TYPE {
+                shogun_sys::TYPE_FLOAT64
+            }
+        }
+
+        impl Features {
+            /// Creates sparse features from a matrix with one sample per
+            /// column, which is Shogun's convention. CSR matrices are
+            /// converted to CSC first.
+            pub fn from_sparse<T: SparseElement>(matrix: &CsMat<T>) -> Result<Features, ShogunError> {
+                Features::from_sparse_with_layout(matrix, SampleLayout::SamplesAsColumns)
+            }
+
+            /// Creates sparse features from a matrix, with the samples
+            /// stored as described by layout. No conversion is needed when
+            /// the samples are the outer dimension, i.e. CSC for
+            /// SamplesAsColumns and CSR for SamplesAsRows.
+            pub fn from_sparse_with_layout<T: SparseElement>(matrix: &CsMat<T>, layout: SampleLayout) -> Result<Features, ShogunError> {
+                // Shogun stores one sparse vector per sample, so the matrix is
+                // only converted when the samples are its inner dimension
+                let (num_features, num_vectors, storage) = match layout {
+                    SampleLayout::SamplesAsColumns => (matrix.rows(), matrix.cols(), CompressedStorage::CSC),
+                    SampleLayout::SamplesAsRows => (matrix.cols(), matrix.rows(), CompressedStorage::CSR),
+                };
+                let num_features = u32::try_from(num_features)
+                    .map_err(|_| ShogunError::InvalidInput(format!("Too many features for Shogun: {}", num_features)))?;
+                let num_vectors = u32::try_from(num_vectors)
+                    .map_err(|_| ShogunError::InvalidInput(format!("Too many samples for Shogun: {}", num_vectors)))?;
+                let converted;
+                let compressed = if matrix.storage() == storage {
+                    matrix.view()
+                } else {
+                    converted = match storage {
+                        CompressedStorage::CSC => matrix.to_csc(),
+                        CompressedStorage::CSR => matrix.to_csr(),
+                    };
+                    converted.view()
+                };
+                let offset = compressed.indptr()[0];
+                let indptr: Vec<u64> = compressed.indptr().iter().map(|&idx| (idx - offset) as u64).collect();
+                let indices: Vec<u64> = compressed.indices().iter().map(|&idx| idx as u64).collect();
+                unsafe {
+                    let type_erased_data = compressed.data().as_ptr() as *const std::ffi::c_void;
+                    let c_ptr = shogun_sys::create_sparse_features(
+                        indptr.as_ptr(), indices.as_ptr(), type_erased_data,
+                        num_features, num_vectors, T::c_type());
+                    handle_sgobject_result::<Features>(&c_ptr)
+                }
+            }
+
+            /// Copies sparse features into a CSC matrix of shape
+            /// (num_features, num_vectors). Fails with TypeMismatch if the
+            /// features are not sparse features of type T.
+            pub fn to_sparse<T: SparseElement>(&self) -> Result<CsMat<T>, ShogunError> {
+                let result = unsafe { shogun_sys::get_sparse_feature_matrix(self.ptr) };
+                let c_matrix = details::handle_sparse_matrix_result(&result)?;
+                unsafe {
+                    let matrix_type = shogun_sys::get_sparse_matrix_type(c_matrix);
+                    let num_features = shogun_sys::get_sparse_matrix_num_features(c_matrix) as usize;
+                    let num_vectors = shogun_sys::get_sparse_matrix_num_vectors(c_matrix) as usize;
+                    let nnz = shogun_sys::get_sparse_matrix_nnz(c_matrix) as usize;
+                    if matrix_type != T::c_type() {
+                        shogun_sys::destroy_sparse_matrix(c_matrix);
+                        return Err(ShogunError::TypeMismatch("sparse features have a different element type".to_string()));
+                    }
+                    let indptr = std::slice::from_raw_parts(shogun_sys::get_sparse_matrix_indptr(c_matrix), num_vectors + 1)
+                        .iter().map(|&idx| idx as usize).collect();
+                    let indices = std::slice::from_raw_parts(shogun_sys::get_sparse_matrix_indices(c_matrix), nnz)
+                        .iter().map(|&idx| idx as usize).collect();
+                    let data = std::slice::from_raw_parts(shogun_sys::get_sparse_matrix_data(c_matrix) as *const T, nnz).to_vec();
+                    shogun_sys::destroy_sparse_matrix(c_matrix);
+                    CsMat::try_new_csc((num_features, num_vectors), indptr, indices, data)
+                        .map_err(|err| ShogunError::InvalidInput(format!("Invalid sparse matrix: {}", err)))
+                }
+            }
+        }
+    }
 }
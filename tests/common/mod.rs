@@ -0,0 +1,4 @@
+/// Path of a file in the toy data set of the shogun-data submodule
+pub fn toy_data(name: &str) -> String {
+    format!("{}/shogun-data/toy/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
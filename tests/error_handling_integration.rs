@@ -0,0 +1,123 @@
+use shogun::shogun::{File, Features, Kernel, Machine, Distance, CombinationRule, Evaluation, Labels, SGObject, ShogunError};
+use ndarray::{arr1, arr2};
+
+mod common;
+
+use common::toy_data;
+
+#[test]
+fn get_missing_parameter() -> Result<(), ShogunError> {
+    let gaussian = Kernel::new("GaussianKernel")?;
+
+    match gaussian.get::<f64>("not_a_parameter") {
+        Err(ShogunError::ParameterNotFound(_)) => (),
+        Err(other) => panic!("Expected ParameterNotFound, got {}", other),
+        Ok(_) => panic!("not_a_parameter should not exist"),
+    }
+    assert!(gaussian.get_any("not_a_parameter").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn put_missing_parameter() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    assert!(gaussian.put("not_a_parameter", &1.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn names_with_null_bytes() -> Result<(), ShogunError> {
+    match Kernel::new("Gaussian\0Kernel") {
+        Err(ShogunError::InvalidInput(_)) => (),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("Class names with null bytes should be rejected"),
+    }
+
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    assert!(gaussian.put("log\0width", &1.0).is_err());
+    assert!(gaussian.get::<f64>("log\0width").is_err());
+    assert!(gaussian.get_any("log\0width").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn put_array_of_wrong_type() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    assert!(gaussian.put("log_width", &arr2(&[[1.0, 2.0], [3.0, 4.0]])).is_err());
+    assert!(gaussian.put("log_width", &arr1(&[1.0, 2.0])).is_err());
+    assert!(gaussian.put("not_a_parameter", &arr1(&[1.0, 2.0])).is_err());
+    Ok(())
+}
+
+#[test]
+fn create_unknown_classes() {
+    fn assert_unknown_class<T>(result: Result<T, ShogunError>) {
+        match result {
+            Err(ShogunError::UnknownClass(_)) => (),
+            Err(other) => panic!("Expected UnknownClass, got {}", other),
+            Ok(_) => panic!("NotAClass should not be registered"),
+        }
+    }
+
+    assert_unknown_class(Machine::new("NotAClass"));
+    assert_unknown_class(Kernel::new("NotAClass"));
+    assert_unknown_class(Distance::new("NotAClass"));
+    assert_unknown_class(Features::new("NotAClass"));
+    assert_unknown_class(File::new("NotAClass"));
+    assert_unknown_class(CombinationRule::new("NotAClass"));
+    assert_unknown_class(Evaluation::new("NotAClass"));
+    assert!(Labels::new("NotAClass").is_err());
+}
+
+#[test]
+fn apply_untrained_machine() -> Result<(), ShogunError> {
+    let features_test = Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_test.dat"))?)?;
+    let rand_forest = Machine::new("RandomForest")?;
+
+    assert!(rand_forest.apply(&features_test).is_err());
+    assert!(rand_forest.apply_multiclass(&features_test).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn init_kernel_with_incompatible_features() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    let lhs = Features::from_array(&arr2(&[[1.0, 2.0], [3.0, 4.0]]))?;
+    let rhs = Features::from_array(&arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]))?;
+
+    assert!(gaussian.init(&lhs, &rhs).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn train_with_mismatched_labels() -> Result<(), ShogunError> {
+    let features_test = Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_test.dat"))?)?;
+    let labels_train = Labels::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_labels_train.dat"))?)?;
+
+    let mut rand_forest = Machine::new("RandomForest")?;
+    rand_forest.put("labels", &labels_train)?;
+
+    assert!(rand_forest.train(&features_test).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn evaluate_mismatched_labels() -> Result<(), ShogunError> {
+    let labels_train = Labels::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_labels_train.dat"))?)?;
+    let labels_test = Labels::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_labels_test.dat"))?)?;
+
+    let acc = Evaluation::new("MulticlassAccuracy")?;
+    assert!(acc.evaluate(&labels_train, &labels_test).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn read_missing_file() {
+    assert!(File::read_csv(toy_data("does_not_exist.dat")).is_err());
+}
@@ -0,0 +1,56 @@
+use shogun::shogun::{File, Features, Machine, CombinationRule, Evaluation, Labels, SGObject, ShogunError, Version, set_num_threads, live_allocations};
+
+// kept as the only test in this binary, since other tests running in
+// parallel would change the number of live allocations
+#[test]
+fn random_forest_does_not_leak() -> Result<(), ShogunError> {
+
+    set_num_threads(1)?;
+
+    let project_root = env!("CARGO_MANIFEST_DIR");
+    let meta_data = format!("{}/shogun-data/toy", project_root);
+
+    let live_before = live_allocations();
+    {
+        let f_feats_train = File::read_csv(format!("{}/classifier_4class_2d_linear_features_train.dat", meta_data))?;
+        let f_feats_test = File::read_csv(format!("{}/classifier_4class_2d_linear_features_test.dat", meta_data))?;
+        let f_labels_train = File::read_csv(format!("{}/classifier_4class_2d_linear_labels_train.dat", meta_data))?;
+        let f_labels_test = File::read_csv(format!("{}/classifier_4class_2d_linear_labels_test.dat", meta_data))?;
+
+        let features_train = Features::from_file(&f_feats_train)?;
+        let features_test = Features::from_file(&f_feats_test)?;
+        let labels_train = Labels::from_file(&f_labels_train)?;
+        let labels_test = Labels::from_file(&f_labels_test)?;
+
+        let mut rand_forest = Machine::new("RandomForest")?;
+        let m_vote = CombinationRule::new("MajorityVote")?;
+
+        rand_forest.put("labels", &labels_train)?;
+        rand_forest.put("num_bags", &100)?;
+        rand_forest.put("combination_rule", &m_vote)?;
+        rand_forest.put("seed", &1)?;
+
+        rand_forest.train(&features_train)?;
+
+        let predictions = rand_forest.apply(&features_test)?;
+
+        let acc = Evaluation::new("MulticlassAccuracy")?;
+        acc.evaluate(&predictions, &labels_test)?;
+
+        // getters, string representations and errors also go through the C API
+        rand_forest.get::<i32>("num_bags")?;
+        rand_forest.get::<CombinationRule>("combination_rule")?;
+        rand_forest.get::<f64>("num_bags").unwrap_err();
+        rand_forest.put("not_a_parameter", &1).unwrap_err();
+        assert!(Machine::new("RandomForests").is_err());
+        SGObject::to_string(&rand_forest);
+        rand_forest.parameters()?;
+        rand_forest.deep_clone()?;
+        rand_forest.share()?;
+        Version::new().main_version()?;
+    }
+
+    assert_eq!(live_allocations(), live_before);
+
+    Ok(())
+}
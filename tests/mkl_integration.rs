@@ -0,0 +1,137 @@
+use shogun::shogun::{CombinedFeatures, CombinedKernel, Evaluation, File, Features, Kernel, Labels, MKLClassification, MKLMulticlass, SGObject, ShogunError, set_num_threads};
+use ndarray::{arr2, Array1};
+
+mod common;
+
+use common::toy_data;
+
+/// A Gaussian and a linear kernel, which start with the same weight
+fn gaussian_and_linear_kernel() -> Result<CombinedKernel, ShogunError> {
+    CombinedKernel::new()?
+        .append(&Kernel::new("GaussianKernel")?)?
+        .append(&Kernel::new("LinearKernel")?)
+}
+
+fn assert_not_uniform(weights: &Array1<f64>) {
+    assert!(weights.iter().any(|&weight| (weight - weights[0]).abs() > 1e-6),
+        "Training did not change the subkernel weights {}", weights);
+}
+
+#[test]
+fn combined_features_and_kernel() -> Result<(), ShogunError> {
+    let numeric = Features::from_array(&arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]))?;
+    let more_numeric = Features::from_array(&arr2(&[[0.0, 1.0, 0.0]]))?;
+    let combined = CombinedFeatures::new()?
+        .append(&numeric)?
+        .append(&more_numeric)?;
+
+    let mut kernel = CombinedKernel::new()?
+        .append(&Kernel::new("GaussianKernel")?)?
+        .append(&Kernel::new("LinearKernel")?)?;
+    kernel.init(&combined, &combined)?;
+    assert_eq!(kernel.subkernel_weights()?.len(), 2);
+
+    // every part needs the same number of samples
+    let too_short = Features::from_array(&arr2(&[[1.0, 2.0]]))?;
+    assert!(CombinedFeatures::new()?.append(&numeric)?.append(&too_short).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn mkl_multiclass() -> Result<(), ShogunError> {
+    set_num_threads(1)?;
+
+    let features_train = Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_train.dat"))?)?;
+    let labels_train = Labels::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_labels_train.dat"))?)?;
+
+    let combined = CombinedFeatures::new()?
+        .append(&features_train)?
+        .append(&features_train)?;
+    let kernel = gaussian_and_linear_kernel()?;
+
+    let mut mkl = MKLMulticlass::new()?;
+    mkl.set_kernel(&kernel)?;
+    mkl.set_labels(&labels_train)?;
+    mkl.train(&combined)?;
+
+    // the machine trains the kernel that was put, not a copy
+    let weights = kernel.subkernel_weights()?;
+    assert_eq!(weights.len(), 2);
+    assert!(weights.iter().all(|&weight| weight >= 0.0));
+    assert_not_uniform(&weights);
+
+    Ok(())
+}
+
+#[test]
+fn mkl_classification() -> Result<(), ShogunError> {
+    set_num_threads(1)?;
+
+    let features_train = Features::from_file(&File::read_csv(toy_data("classifier_binary_2d_linear_features_train.dat"))?)?;
+    let features_test = Features::from_file(&File::read_csv(toy_data("classifier_binary_2d_linear_features_test.dat"))?)?;
+    let labels_train = Labels::from_file(&File::read_csv(toy_data("classifier_binary_2d_linear_labels_train.dat"))?)?;
+    let labels_test = Labels::from_file(&File::read_csv(toy_data("classifier_binary_2d_linear_labels_test.dat"))?)?;
+
+    let combined_train = CombinedFeatures::new()?
+        .append(&features_train)?
+        .append(&features_train)?;
+    let combined_test = CombinedFeatures::new()?
+        .append(&features_test)?
+        .append(&features_test)?;
+    let kernel = gaussian_and_linear_kernel()?;
+
+    // the 1-norm needs an LP solver, the 2-norm has a closed form
+    let mut mkl = MKLClassification::new()?;
+    mkl.set_mkl_norm(2.0)?;
+    mkl.set_kernel(&kernel)?;
+    mkl.set_labels(&labels_train)?;
+    mkl.train(&combined_train)?;
+    assert_not_uniform(&kernel.subkernel_weights()?);
+
+    let predictions = mkl.apply(&combined_test)?;
+    let accuracy = Evaluation::new("AccuracyMeasure")?.evaluate(&predictions, &labels_test)?;
+    assert!(accuracy > 0.5, "MKL should beat chance, got an accuracy of {}", accuracy);
+
+    Ok(())
+}
+
+#[test]
+fn mkl_classification_parameters() -> Result<(), ShogunError> {
+    let kernel = gaussian_and_linear_kernel()?;
+    let labels = Labels::from_file(&File::read_csv(toy_data("classifier_binary_2d_linear_labels_train.dat"))?)?;
+
+    let mut mkl = MKLClassification::new()?;
+    mkl.set_c_mkl(0.5)?;
+    assert_eq!(mkl.c_mkl()?, 0.5);
+    mkl.set_mkl_norm(2.0)?;
+    assert_eq!(mkl.mkl_norm()?, 2.0);
+    mkl.set_mkl_epsilon(1e-4)?;
+    assert_eq!(mkl.mkl_epsilon()?, 1e-4);
+    mkl.set_kernel(&kernel)?;
+    assert!(mkl.kernel()?.equals(&*kernel)?);
+    mkl.set_labels(&labels)?;
+    assert!(mkl.labels()?.equals(&labels)?);
+
+    Ok(())
+}
+
+#[test]
+fn mkl_multiclass_parameters() -> Result<(), ShogunError> {
+    let kernel = gaussian_and_linear_kernel()?;
+    let labels = Labels::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_labels_train.dat"))?)?;
+
+    let mut mkl = MKLMulticlass::new()?;
+    mkl.set_mkl_eps(1e-3)?;
+    assert_eq!(mkl.mkl_eps()?, 1e-3);
+    mkl.set_max_num_mkl_iters(10)?;
+    assert_eq!(mkl.max_num_mkl_iters()?, 10);
+    mkl.set_mkl_norm(2.0)?;
+    assert_eq!(mkl.mkl_norm()?, 2.0);
+    mkl.set_kernel(&kernel)?;
+    assert!(mkl.kernel()?.equals(&*kernel)?);
+    mkl.set_labels(&labels)?;
+    assert!(mkl.labels()?.equals(&labels)?);
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
-use shogun::shogun::{File, Features, Machine, CombinationRule, Evaluation, Labels, SGObject, set_num_threads};
+use shogun::shogun::{File, Features, Machine, CombinationRule, Evaluation, Labels, SGObject, ShogunError, set_num_threads};
 
 #[test]
-fn random_forest() -> Result<(), String> {
+fn random_forest() -> Result<(), ShogunError> {
 
-    set_num_threads(1);
+    set_num_threads(1)?;
 
     let project_root = env!("CARGO_MANIFEST_DIR");
     let meta_data = format!("{}/shogun-data/toy", project_root);
@@ -34,9 +34,87 @@ fn random_forest() -> Result<(), String> {
     let accuracy = acc.evaluate(&predictions, &labels_test)?;
 
     // there is an issue with reproducing results
-    if accuracy > 0.7 {
-        Ok(())
-    } else {
-        Err("Expected an accuracy of at least 0.7".to_string())
+    assert!(accuracy > 0.7, "Expected an accuracy of at least 0.7");
+
+    Ok(())
+}
+
+#[test]
+fn random_forest_validation_split() -> Result<(), ShogunError> {
+
+    set_num_threads(1)?;
+
+    let project_root = env!("CARGO_MANIFEST_DIR");
+    let meta_data = format!("{}/shogun-data/toy", project_root);
+
+    let mut features = Features::from_file(&File::read_csv(format!("{}/classifier_4class_2d_linear_features_train.dat", meta_data))?)?;
+    let mut labels = Labels::from_file(&File::read_csv(format!("{}/classifier_4class_2d_linear_labels_train.dat", meta_data))?)?;
+
+    let num_samples = features.num_vectors()?;
+    let train_indices: Vec<usize> = (0..num_samples).filter(|idx| idx % 4 != 0).collect();
+    let validation_indices: Vec<usize> = (0..num_samples).filter(|idx| idx % 4 == 0).collect();
+
+    let mut rand_forest = Machine::builder("RandomForest")
+        .set("num_bags", 100)
+        .set("combination_rule", CombinationRule::new("MajorityVote")?)
+        .set("seed", 1)
+        .build()?;
+
+    {
+        let features_train = features.with_subset(&train_indices)?;
+        let labels_train = labels.with_subset(&train_indices)?;
+        assert_eq!(features_train.num_vectors()?, train_indices.len());
+        rand_forest.put("labels", &*labels_train)?;
+        rand_forest.train(&features_train)?;
     }
-}
\ No newline at end of file
+
+    let features_validation = features.with_subset(&validation_indices)?;
+    let labels_validation = labels.with_subset(&validation_indices)?;
+    let predictions = rand_forest.apply(&features_validation)?;
+
+    let acc = Evaluation::new("MulticlassAccuracy")?;
+    let accuracy = acc.evaluate(&predictions, &labels_validation)?;
+    assert!(accuracy > 0.7, "Expected an accuracy of at least 0.7");
+
+    Ok(())
+}
+
+#[test]
+fn random_forest_builder() -> Result<(), ShogunError> {
+
+    set_num_threads(1)?;
+
+    let project_root = env!("CARGO_MANIFEST_DIR");
+    let meta_data = format!("{}/shogun-data/toy", project_root);
+
+    let f_feats_train = File::read_csv(format!("{}/classifier_4class_2d_linear_features_train.dat", meta_data))?;
+    let f_feats_test = File::read_csv(format!("{}/classifier_4class_2d_linear_features_test.dat", meta_data))?;
+    let f_labels_train = File::read_csv(format!("{}/classifier_4class_2d_linear_labels_train.dat", meta_data))?;
+    let f_labels_test = File::read_csv(format!("{}/classifier_4class_2d_linear_labels_test.dat", meta_data))?;
+
+    let features_train = Features::from_file(&f_feats_train)?;
+    let features_test = Features::from_file(&f_feats_test)?;
+    let labels_train = Labels::from_file(&f_labels_train)?;
+    let labels_test = Labels::from_file(&f_labels_test)?;
+
+    let m_vote = CombinationRule::new("MajorityVote")?;
+
+    let mut rand_forest = Machine::builder("RandomForest")
+        .set("labels", &labels_train)
+        .set("num_bags", 100)
+        .set("combination_rule", m_vote)
+        .set("seed", 1)
+        .build()?;
+
+    rand_forest.train(&features_train)?;
+
+    let predictions = rand_forest.apply(&features_test)?;
+
+    let acc = Evaluation::new("MulticlassAccuracy")?;
+    let accuracy = acc.evaluate(&predictions, &labels_test)?;
+
+    // there is an issue with reproducing results
+    assert!(accuracy > 0.7, "Expected an accuracy of at least 0.7");
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use shogun::shogun::{File, Features, Kernel, Machine, CombinationRule, Labels, SGObject, ShogunError, set_num_threads};
+
+mod common;
+
+use common::toy_data;
+
+#[test]
+fn json_round_trip() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    gaussian.put("log_width", &2.0)?;
+
+    let json = gaussian.to_json()?;
+    let restored = Kernel::from_json(&json)?;
+    assert!(restored.equals(&gaussian)?);
+
+    // a kernel cannot be loaded as a machine
+    assert!(Machine::from_json(&json).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn random_forest_save_and_load() -> Result<(), ShogunError> {
+    set_num_threads(1)?;
+
+    let features_train = Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_train.dat"))?)?;
+    let features_test = Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_test.dat"))?)?;
+    let labels_train = Labels::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_labels_train.dat"))?)?;
+
+    let mut rand_forest = Machine::builder("RandomForest")
+        .set("labels", &labels_train)
+        .set("num_bags", 10)
+        .set("combination_rule", CombinationRule::new("MajorityVote")?)
+        .set("seed", 1)
+        .build()?;
+    rand_forest.train(&features_train)?;
+    let predictions = rand_forest.apply(&features_test)?;
+
+    let path = std::env::temp_dir().join(format!("shogun_random_forest_{}.json", std::process::id()));
+    rand_forest.save(&path)?;
+    let loaded = Machine::load(&path);
+    std::fs::remove_file(&path).expect("Failed to remove saved model");
+
+    let loaded_predictions = loaded?.apply(&features_test)?;
+    assert!(loaded_predictions.equals(&predictions)?);
+
+    Ok(())
+}
+
+#[test]
+fn load_missing_file() {
+    assert!(Machine::load(toy_data("does_not_exist.json")).is_err());
+}
@@ -0,0 +1,510 @@
+use shogun::shogun::{Alphabet, AnySGObject, CombinationRule, Distance, FeatureClass, FeatureType, Features, File, GaussianKernel, Kernel, Labels, Machine, RandomForest, SampleLayout, SGObject, ShogunError};
+use ndarray::{arr1, arr2, s, Array1, Array2, ShapeBuilder};
+use std::convert::TryFrom;
+
+#[test]
+fn typed_get() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    gaussian.put("log_width", &2.0)?;
+
+    assert_eq!(gaussian.get::<f64>("log_width")?, 2.0);
+    gaussian.get::<Distance>("m_distance")?;
+    assert!(gaussian.get_any("m_distance")?.downcast_ref::<Distance>().is_some());
+
+    Ok(())
+}
+
+#[test]
+fn typed_get_type_mismatch() -> Result<(), ShogunError> {
+    let gaussian = Kernel::new("GaussianKernel")?;
+
+    match gaussian.get::<i32>("log_width") {
+        Err(ShogunError::TypeMismatch(msg)) => assert!(msg.contains("expected i32, got f64"), "{}", msg),
+        Err(other) => panic!("Expected a type mismatch, got {}", other),
+        Ok(_) => panic!("Expected a type mismatch for log_width"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unknown_class() {
+    match Machine::new("RandomForests") {
+        Err(ShogunError::UnknownClass(msg)) => assert!(msg.contains("Did you mean RandomForest?"), "{}", msg),
+        Err(other) => panic!("Expected UnknownClass, got {}", other),
+        Ok(_) => panic!("RandomForests should not exist"),
+    }
+}
+
+#[test]
+fn wrong_base_class() {
+    // registered, but with another base class
+    match Machine::new("GaussianKernel") {
+        Err(ShogunError::TypeMismatch(_)) => (),
+        Err(other) => panic!("Expected TypeMismatch, got {}", other),
+        Ok(_) => panic!("GaussianKernel is not a machine"),
+    }
+    match Kernel::new("RandomForest") {
+        Err(ShogunError::TypeMismatch(_)) => (),
+        Err(other) => panic!("Expected TypeMismatch, got {}", other),
+        Ok(_) => panic!("RandomForest is not a kernel"),
+    }
+}
+
+#[test]
+fn available_classes() {
+    let machines = Machine::available_classes();
+    assert!(machines.iter().any(|name| name == "RandomForest"));
+
+    let kernels = Kernel::available_classes();
+    assert!(kernels.iter().any(|name| name == "GaussianKernel"));
+}
+
+#[test]
+fn put_errors() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+
+    match gaussian.put("not_a_parameter", &1.0) {
+        Err(ShogunError::ParameterNotFound(_)) => (),
+        other => panic!("Expected ParameterNotFound, got {:?}", other),
+    }
+    match gaussian.put("log_width", &1.0f32) {
+        Err(ShogunError::TypeMismatch(_)) => (),
+        other => panic!("Expected TypeMismatch, got {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parameters() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    gaussian.put("log_width", &3.0)?;
+
+    let parameters = gaussian.parameters()?;
+    let log_width = parameters.iter()
+        .find(|param| param.name == "log_width")
+        .expect("GaussianKernel should have a log_width parameter");
+
+    assert!(log_width.hyperparameter);
+    assert!(log_width.settable());
+    assert!(!log_width.description.is_empty());
+    let value = log_width.value()?;
+    assert_eq!(value.downcast_ref::<f64>(), Some(&3.0));
+
+    Ok(())
+}
+
+#[test]
+fn vector_put_and_get() -> Result<(), ShogunError> {
+    let mut machine = Machine::new("LibLinear")?;
+    let w = arr1(&[1.0, -2.0, 3.0]);
+    machine.put("w", &w)?;
+
+    assert_eq!(machine.get::<Array1<f64>>("w")?, w);
+    match machine.put("w", &arr1(&[1.0f32, 2.0])) {
+        Err(ShogunError::TypeMismatch(_)) => (),
+        other => panic!("Expected TypeMismatch, got {:?}", other),
+    }
+
+    // a matrix without rows is still a matrix
+    match machine.put("w", &Array2::<f64>::zeros((0, 3))) {
+        Err(ShogunError::TypeMismatch(_)) => (),
+        other => panic!("Expected TypeMismatch, got {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn matrix_get() -> Result<(), ShogunError> {
+    let matrix = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let features = Features::from_array(&matrix)?;
+
+    assert_eq!(features.get::<Array2<f64>>("feature_matrix")?, matrix);
+
+    Ok(())
+}
+
+#[test]
+fn matrix_memory_layouts() -> Result<(), ShogunError> {
+    let c_order = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let f_order = Array2::from_shape_vec((2, 3).f(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]).unwrap();
+    let wide = arr2(&[[1.0, 0.0, 2.0, 0.0, 3.0], [4.0, 0.0, 5.0, 0.0, 6.0]]);
+    let strided = wide.slice(s![.., ..;2]);
+
+    for features in &[Features::from_array(&f_order)?, Features::from_array(strided)?] {
+        assert_eq!(features.get::<Array2<f64>>("feature_matrix")?, c_order);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn matrix_samples_as_rows() -> Result<(), ShogunError> {
+    // three samples with two features each
+    let samples = arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    let features = Features::from_array_with_layout(&samples, SampleLayout::SamplesAsRows)?;
+
+    assert_eq!(features.get::<Array2<f64>>("feature_matrix")?, samples.t());
+
+    Ok(())
+}
+
+#[test]
+fn borrowed_features() -> Result<(), ShogunError> {
+    let mut matrix = Array2::from_shape_vec((2, 3).f(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]).unwrap();
+    let expected = matrix.clone();
+    let features = Features::from_view_mut(matrix.view_mut())?;
+    assert_eq!(features.num_vectors()?, 3);
+    assert_eq!(features.num_features()?, 2);
+    assert_eq!(features.deep_clone()?.get::<Array2<f64>>("feature_matrix")?, expected);
+
+    // C-order views are copied
+    let mut c_order = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let expected = c_order.clone();
+    let copied = Features::from_view_mut(c_order.view_mut())?;
+    assert_eq!(copied.deep_clone()?.get::<Array2<f64>>("feature_matrix")?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn train_on_borrowed_features() -> Result<(), ShogunError> {
+    let meta_data = format!("{}/shogun-data/toy", env!("CARGO_MANIFEST_DIR"));
+    let features_train = Features::from_file(&File::read_csv(format!("{}/classifier_4class_2d_linear_features_train.dat", meta_data))?)?;
+    let features_test = Features::from_file(&File::read_csv(format!("{}/classifier_4class_2d_linear_features_test.dat", meta_data))?)?;
+    let labels_train = Labels::from_file(&File::read_csv(format!("{}/classifier_4class_2d_linear_labels_train.dat", meta_data))?)?;
+
+    let mut data = features_train.get::<Array2<f64>>("feature_matrix")?;
+    let mut rand_forest = Machine::new("RandomForest")?;
+    rand_forest.put("labels", &labels_train)?;
+    rand_forest.put("num_bags", &10)?;
+    rand_forest.put("combination_rule", &CombinationRule::new("MajorityVote")?)?;
+
+    let detached = {
+        let borrowed = Features::from_view_mut(data.view_mut())?;
+        let trained = rand_forest.train_borrowed(&borrowed)?;
+        assert_eq!(trained.get::<i32>("num_bags")?, 10);
+        assert!(trained.get::<Labels>("labels").is_err());
+        trained.apply(&features_test)?;
+        trained.detach()?
+    };
+    // the detached machine no longer needs the borrowed data
+    data.fill(0.0);
+    detached.apply(&features_test)?;
+
+    Ok(())
+}
+
+#[test]
+fn string_features() -> Result<(), ShogunError> {
+    let sequences = ["ACGTACGT", "acgtttga", "GGCCAATT"];
+    let features = Features::from_strings(&sequences, Alphabet::Dna)?;
+
+    let mut kernel = Kernel::new("WeightedDegreeStringKernel")?;
+    kernel.put("degree", &3)?;
+    kernel.init(&features, &features)?;
+
+    let text: Vec<Vec<u8>> = vec![b"some text".to_vec(), vec![0, 255]];
+    Features::from_strings(&text, Alphabet::RawByte)?;
+
+    // U is not a DNA symbol
+    assert!(Features::from_strings(&["ACGU"], Alphabet::Dna).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn features_metadata() -> Result<(), ShogunError> {
+    let features = Features::from_array(&arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]))?;
+    assert_eq!(features.num_vectors()?, 3);
+    assert_eq!(features.num_features()?, 2);
+    assert_eq!(features.feature_class(), FeatureClass::Dense);
+    assert_eq!(features.feature_type()?, FeatureType::Float64);
+
+    let features = Features::from_array(&arr2(&[[1i32, 2], [3, 4]]))?;
+    assert_eq!(features.feature_type()?, FeatureType::Int32);
+
+    let features = Features::from_strings(&["ACGT", "ACGTACGT"], Alphabet::Dna)?;
+    assert_eq!(features.num_vectors()?, 2);
+    assert_eq!(features.num_features()?, 8);
+    assert_eq!(features.feature_class(), FeatureClass::String);
+    assert_eq!(features.feature_type()?, FeatureType::Char);
+
+    Ok(())
+}
+
+#[test]
+fn features_subset() -> Result<(), ShogunError> {
+    let mut features = Features::from_array(&arr2(&[[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]))?;
+    {
+        let mut subset = features.with_subset(&[3, 1, 2])?;
+        assert_eq!(subset.num_vectors()?, 3);
+        {
+            // indices of a nested subset refer to the outer subset
+            let nested = subset.with_subset(&[0])?;
+            assert_eq!(nested.num_vectors()?, 1);
+        }
+        assert_eq!(subset.num_vectors()?, 3);
+        assert!(subset.with_subset(&[3]).is_err());
+    }
+    assert_eq!(features.num_vectors()?, 4);
+
+    assert!(features.with_subset(&[4]).is_err());
+    assert_eq!(features.num_vectors()?, 4);
+
+    Ok(())
+}
+
+#[test]
+fn runtime_names() -> Result<(), ShogunError> {
+    let class_name = String::from("GaussianKernel");
+    let parameter_name = format!("log_{}", "width");
+
+    let mut gaussian = Kernel::new(&class_name)?;
+    gaussian.put(&parameter_name, &3.0)?;
+    assert_eq!(gaussian.get::<f64>(&parameter_name)?, 3.0);
+
+    Ok(())
+}
+
+#[test]
+fn deep_clone() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    gaussian.put("log_width", &2.0)?;
+
+    let mut clone = gaussian.deep_clone()?;
+    assert_eq!(clone.get::<f64>("log_width")?, 2.0);
+
+    clone.put("log_width", &3.0)?;
+    assert_eq!(gaussian.get::<f64>("log_width")?, 2.0);
+
+    Ok(())
+}
+
+#[test]
+fn share() -> Result<(), ShogunError> {
+    let gaussian = Kernel::new("GaussianKernel")?;
+    let mut shared = gaussian.share()?;
+
+    shared.put("log_width", &3.0)?;
+    assert_eq!(gaussian.get::<f64>("log_width")?, 3.0);
+
+    Ok(())
+}
+
+#[test]
+fn equals() -> Result<(), ShogunError> {
+    let gaussian = Kernel::new("GaussianKernel")?;
+    let mut clone = gaussian.deep_clone()?;
+    assert!(gaussian.equals(&clone)?);
+
+    clone.put("log_width", &3.0)?;
+    assert!(!gaussian.equals(&clone)?);
+    assert!(!gaussian.equals(&Kernel::new("LinearKernel")?)?);
+
+    Ok(())
+}
+
+#[cfg(feature = "partial-eq")]
+#[test]
+fn partial_eq() -> Result<(), ShogunError> {
+    let gaussian = Kernel::new("GaussianKernel")?;
+    let mut clone = gaussian.deep_clone()?;
+    assert!(gaussian == clone);
+
+    clone.put("log_width", &3.0)?;
+    assert!(gaussian != clone);
+
+    Ok(())
+}
+
+#[test]
+fn bool_put_and_get() -> Result<(), ShogunError> {
+    let mut machine = Machine::new("LibLinear")?;
+    machine.put("use_bias", &false)?;
+    assert_eq!(machine.get::<bool>("use_bias")?, false);
+    machine.put("use_bias", &true)?;
+    assert_eq!(machine.get::<bool>("use_bias")?, true);
+
+    Ok(())
+}
+
+#[test]
+fn string_get() -> Result<(), ShogunError> {
+    let project_root = env!("CARGO_MANIFEST_DIR");
+    let filename = format!("{}/shogun-data/toy/classifier_4class_2d_linear_features_train.dat", project_root);
+    let file = File::read_csv(filename.clone())?;
+
+    assert_eq!(file.get::<String>("filename")?, filename);
+
+    Ok(())
+}
+
+#[test]
+fn enum_put_and_get() -> Result<(), ShogunError> {
+    let mut machine = Machine::new("LibLinear")?;
+    machine.put("liblinear_solver_type", "L2R_LR")?;
+    assert_eq!(machine.get::<String>("liblinear_solver_type")?, "L2R_LR");
+
+    match machine.put("liblinear_solver_type", "NOT_A_SOLVER") {
+        Err(ShogunError::InvalidInput(msg)) => assert!(msg.contains("L2R_LR"), "{}", msg),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("NOT_A_SOLVER should not be accepted"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn non_ascii_char_put() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    match gaussian.put("log_width", &'é') {
+        Err(ShogunError::InvalidInput(_)) => Ok(()),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("Non ASCII characters should not be accepted"),
+    }
+}
+
+#[test]
+fn any_sgobject() -> Result<(), ShogunError> {
+    let mut rand_forest = Machine::new("RandomForest")?;
+    rand_forest.put("combination_rule", &CombinationRule::new("MajorityVote")?)?;
+
+    let rule = rand_forest.get::<AnySGObject>("combination_rule")?;
+    assert_eq!(rule.base_type(), "CombinationRule");
+    assert_eq!(rule.class_name(), "MajorityVote");
+    assert!(Kernel::try_from(rule).is_err());
+
+    let rule = rand_forest.get_any("combination_rule")?;
+    let rule = rule.downcast::<CombinationRule>().expect("Expected a CombinationRule");
+    assert_eq!(rule.class_name(), "MajorityVote");
+
+    Ok(())
+}
+
+#[test]
+fn unset_object_parameter() -> Result<(), ShogunError> {
+    // lhs is only set by init
+    let gaussian = Kernel::new("GaussianKernel")?;
+    match gaussian.get::<Features>("lhs") {
+        Err(ShogunError::InvalidInput(_)) => (),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("lhs should not be set"),
+    }
+    assert!(gaussian.get::<AnySGObject>("lhs").is_err());
+    assert!(gaussian.get_any("lhs").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn builder() -> Result<(), ShogunError> {
+    let gaussian = Kernel::builder("GaussianKernel")
+        .set("log_width", 2.0)
+        .set("cache_size", 20)
+        .build()?;
+
+    assert_eq!(gaussian.get::<f64>("log_width")?, 2.0);
+    assert_eq!(gaussian.get::<i32>("cache_size")?, 20);
+
+    Ok(())
+}
+
+#[test]
+fn builder_reports_all_errors() {
+    let result = Kernel::builder("GaussianKernel")
+        .set("not_a_parameter", 2.0)
+        .set("log_width", 2)
+        .set("cache_size", 20)
+        .build();
+
+    match result {
+        Err(ShogunError::Multiple(errors)) => {
+            assert_eq!(errors.len(), 2);
+            assert!(matches!(errors[0], ShogunError::ParameterNotFound(_)));
+            assert!(matches!(errors[1], ShogunError::TypeMismatch(_)));
+        },
+        Err(other) => panic!("Expected all errors, got {}", other),
+        Ok(_) => panic!("Expected the builder to fail"),
+    }
+}
+
+#[test]
+fn typed_wrappers() -> Result<(), ShogunError> {
+    let mut gaussian = GaussianKernel::new()?;
+    gaussian.set_log_width(2.0)?;
+    assert_eq!(gaussian.log_width()?, 2.0);
+    assert_eq!(gaussian.get::<f64>("log_width")?, 2.0);
+    assert_eq!(gaussian.distance()?.class_name(), "EuclideanDistance");
+
+    let mut rand_forest = RandomForest::new()?;
+    rand_forest.set_num_bags(10)?;
+    rand_forest.set_combination_rule(&CombinationRule::new("MajorityVote")?)?;
+    assert_eq!(rand_forest.combination_rule()?.class_name(), "MajorityVote");
+
+    let machine: Machine = rand_forest.into();
+    assert_eq!(machine.get::<i32>("num_bags")?, 10);
+    assert!(RandomForest::try_from(machine).is_ok());
+    assert!(GaussianKernel::try_from(Kernel::new("LinearKernel")?).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn unsigned_put_and_get() -> Result<(), ShogunError> {
+    // the kernel properties are a u64 bit field
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    let properties = gaussian.get::<u64>("properties")?;
+    gaussian.put("properties", &u64::MAX)?;
+    assert_eq!(gaussian.get::<u64>("properties")?, u64::MAX);
+    gaussian.put("properties", &properties)?;
+    assert_eq!(gaussian.get::<u64>("properties")?, properties);
+
+    // the buffer size of the solver is a u32
+    let mut machine = Machine::new("DualLibQPBMSOSVM")?;
+    machine.put("m_BufSize", &2000u64)?;
+    assert_eq!(machine.get::<u32>("m_BufSize")?, 2000);
+    machine.put("m_BufSize", &u64::from(u32::MAX))?;
+    assert_eq!(machine.get::<u32>("m_BufSize")?, u32::MAX);
+    match machine.put("m_BufSize", &(u64::from(u32::MAX) + 1)) {
+        Err(ShogunError::InvalidInput(_)) => (),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("Values above u32::MAX should not be accepted"),
+    }
+    assert_eq!(machine.get::<u32>("m_BufSize")?, u32::MAX);
+
+    Ok(())
+}
+
+#[test]
+fn dense_too_many_samples() {
+    // broadcasting repeats the element without allocating the samples
+    let single = arr2(&[[1.0]]);
+    let matrix = single.broadcast((1, 1 << 33)).unwrap();
+    match Features::from_array(matrix) {
+        Err(ShogunError::InvalidInput(_)) => (),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("Shogun cannot index more than u32::MAX samples"),
+    }
+}
+
+#[test]
+fn too_many_strings() {
+    // a zero sized string type, so the array takes no memory
+    #[derive(Clone, Copy)]
+    struct Empty;
+    impl AsRef<[u8]> for Empty {
+        fn as_ref(&self) -> &[u8] {
+            &[]
+        }
+    }
+
+    let strings = [Empty; (1 << 32) + 1];
+    match Features::from_strings(&strings, Alphabet::Dna) {
+        Err(ShogunError::InvalidInput(_)) => (),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("Shogun cannot index more than u32::MAX strings"),
+    }
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "serde")]
+
+use shogun::shogun::{CombinedKernel, Kernel, ParameterSnapshot, ParameterValue, SGObject, ShogunError};
+
+#[test]
+fn snapshot_round_trip() -> Result<(), ShogunError> {
+    let mut gaussian = Kernel::new("GaussianKernel")?;
+    gaussian.put("log_width", &2.0)?;
+
+    let snapshot = ParameterSnapshot::new(&gaussian)?;
+    assert_eq!(snapshot.base_type, "Kernel");
+    assert_eq!(snapshot.class_name, "GaussianKernel");
+    assert_eq!(snapshot.parameters.get("log_width"), Some(&ParameterValue::Float64(2.0)));
+    match snapshot.parameters.get("m_distance") {
+        Some(ParameterValue::Object(distance)) => assert_eq!(distance.base_type, "Distance"),
+        other => panic!("Expected a nested distance snapshot, got {:?}", other),
+    }
+    // unset before init
+    assert!(!snapshot.parameters.contains_key("lhs"));
+
+    let json = serde_json::to_string(&snapshot).expect("Failed to serialize snapshot");
+    let deserialized: ParameterSnapshot = serde_json::from_str(&json).expect("Failed to deserialize snapshot");
+    assert_eq!(deserialized, snapshot);
+
+    let restored: Kernel = deserialized.restore()?;
+    assert_eq!(restored.get::<f64>("log_width")?, 2.0);
+    assert!(restored.equals(&gaussian)?);
+
+    Ok(())
+}
+
+#[test]
+fn snapshot_unsupported_parameter() -> Result<(), ShogunError> {
+    // the subkernels are stored in a vector of objects, which cannot be read
+    let combined = CombinedKernel::new()?
+        .append(&Kernel::new("GaussianKernel")?)?;
+    assert!(combined.get_any("kernel_array").is_err());
+    assert!(ParameterSnapshot::new(&*combined).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "sprs")]
+
+use shogun::shogun::{FeatureClass, Features, Kernel, SampleLayout, ShogunError};
+use ndarray::arr2;
+use sprs::{CsMat, TriMat};
+
+// three features, four samples
+fn samples_as_columns() -> CsMat<f64> {
+    let mut triplets = TriMat::new((3, 4));
+    triplets.add_triplet(0, 0, 1.0);
+    triplets.add_triplet(2, 0, 2.0);
+    triplets.add_triplet(1, 1, 3.0);
+    triplets.add_triplet(0, 3, 4.0);
+    triplets.add_triplet(2, 3, 5.0);
+    triplets.to_csc()
+}
+
+#[test]
+fn sparse_round_trip() -> Result<(), ShogunError> {
+    let matrix = samples_as_columns();
+    let features = Features::from_sparse(&matrix)?;
+    assert_eq!(features.to_sparse::<f64>()?, matrix);
+    assert_eq!(features.feature_class(), FeatureClass::Sparse);
+    assert_eq!(features.num_vectors()?, 4);
+    assert_eq!(features.num_features()?, 3);
+
+    // CSR input is converted
+    let features = Features::from_sparse(&matrix.to_csr())?;
+    assert_eq!(features.to_sparse::<f64>()?, matrix);
+
+    Ok(())
+}
+
+#[test]
+fn sparse_samples_as_rows() -> Result<(), ShogunError> {
+    let matrix = samples_as_columns();
+    let transposed = matrix.transpose_view().to_csr();
+    let features = Features::from_sparse_with_layout(&transposed, SampleLayout::SamplesAsRows)?;
+    assert_eq!(features.to_sparse::<f64>()?, matrix);
+
+    Ok(())
+}
+
+#[test]
+fn sparse_type_mismatch() -> Result<(), ShogunError> {
+    let features = Features::from_sparse(&samples_as_columns())?;
+    assert!(features.to_sparse::<f32>().is_err());
+
+    let dense = Features::from_array(&arr2(&[[1.0, 2.0], [3.0, 4.0]]))?;
+    assert!(dense.to_sparse::<f64>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn sparse_too_many_features() {
+    let matrix = CsMat::<f64>::new_csc((1 << 33, 1), vec![0, 0], vec![], vec![]);
+    match Features::from_sparse(&matrix) {
+        Err(ShogunError::InvalidInput(_)) => (),
+        Err(other) => panic!("Expected InvalidInput, got {}", other),
+        Ok(_) => panic!("Shogun cannot index more than u32::MAX features"),
+    }
+}
+
+#[test]
+fn sparse_kernel() -> Result<(), ShogunError> {
+    let features = Features::from_sparse(&samples_as_columns())?;
+    let mut linear = Kernel::new("LinearKernel")?;
+    linear.init(&features, &features)?;
+
+    Ok(())
+}
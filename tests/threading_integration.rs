@@ -0,0 +1,105 @@
+use shogun::shogun::{File, Features, Machine, CombinationRule, Labels, SGObject, ShogunError, set_num_threads};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+use common::toy_data;
+
+fn trained_random_forest() -> Result<Machine, ShogunError> {
+    random_forest_with_bags(10)
+}
+
+// every call loads its own data, so the machines share no native objects
+fn random_forest_with_bags(num_bags: i32) -> Result<Machine, ShogunError> {
+    let features_train = Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_train.dat"))?)?;
+    let labels_train = Labels::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_labels_train.dat"))?)?;
+
+    let mut rand_forest = Machine::new("RandomForest")?;
+    rand_forest.put("labels", &labels_train)?;
+    rand_forest.put("num_bags", &num_bags)?;
+    rand_forest.put("combination_rule", &CombinationRule::new("MajorityVote")?)?;
+    rand_forest.put("seed", &1)?;
+    rand_forest.train(&features_train)?;
+
+    Ok(rand_forest)
+}
+
+#[test]
+fn apply_from_several_threads() -> Result<(), ShogunError> {
+    set_num_threads(1)?;
+
+    let rand_forest = Arc::new(trained_random_forest()?);
+    let features_test = Arc::new(Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_test.dat"))?)?);
+    let expected = rand_forest.apply(&features_test)?;
+
+    let handles: Vec<_> = (0..4).map(|_| {
+        let rand_forest = Arc::clone(&rand_forest);
+        let features_test = Arc::clone(&features_test);
+        thread::spawn(move || rand_forest.apply(&features_test))
+    }).collect();
+
+    for handle in handles {
+        let predictions = handle.join().expect("Worker thread panicked")?;
+        assert!(predictions.equals(&expected)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn train_in_another_thread() -> Result<(), ShogunError> {
+    let rand_forest = thread::spawn(trained_random_forest).join().expect("Worker thread panicked")?;
+    rand_forest.get::<i32>("num_bags")?;
+
+    Ok(())
+}
+
+#[test]
+fn read_parameters_while_applying() -> Result<(), ShogunError> {
+    set_num_threads(1)?;
+
+    let rand_forest = Arc::new(trained_random_forest()?);
+    let features_test = Arc::new(Features::from_file(&File::read_csv(toy_data("classifier_4class_2d_linear_features_test.dat"))?)?);
+    let combination_rule = rand_forest.get::<CombinationRule>("combination_rule")?;
+
+    let predicting = {
+        let rand_forest = Arc::clone(&rand_forest);
+        let features_test = Arc::clone(&features_test);
+        thread::spawn(move || rand_forest.apply(&features_test).map(|_| ()))
+    };
+    for _ in 0..10 {
+        assert_eq!(rand_forest.get::<i32>("num_bags")?, 10);
+        assert!(!format!("{}", combination_rule).is_empty());
+    }
+    predicting.join().expect("Worker thread panicked")?;
+
+    Ok(())
+}
+
+#[test]
+fn train_independent_machines_in_parallel() -> Result<(), ShogunError> {
+    set_num_threads(1)?;
+
+    let slow_done = Arc::new(AtomicBool::new(false));
+    let slow = {
+        let slow_done = Arc::clone(&slow_done);
+        thread::spawn(move || {
+            let result = random_forest_with_bags(1000).map(|_| ());
+            slow_done.store(true, Ordering::SeqCst);
+            result
+        })
+    };
+
+    // unrelated machines do not wait for each other, so the small forest
+    // is trained while the large one is still training
+    thread::sleep(Duration::from_millis(100));
+    random_forest_with_bags(1)?;
+    assert!(!slow_done.load(Ordering::SeqCst), "Training the small forest waited for the large one");
+
+    slow.join().expect("Worker thread panicked")?;
+
+    Ok(())
+}